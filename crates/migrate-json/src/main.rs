@@ -191,9 +191,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 continue;
             }
 
-            // Insert project
+            // Insert project (date column is a DATE; JSON files hold MM.DD.YYYY strings)
             let project_id: i32 = sqlx::query_scalar(
-                "INSERT INTO projects (title, date, year, path) VALUES ($1, $2, $3, $4) RETURNING id"
+                "INSERT INTO projects (title, date, year, path)
+                 VALUES ($1, to_date($2, 'MM.DD.YYYY'), $3, $4) RETURNING id"
             )
             .bind(&proj.title)
             .bind(&proj.date)
@@ -0,0 +1,106 @@
+-- Named project templates: each defines the categories and checklist items a new
+-- project starts with (works, services, supplies, framework agreements, ...).
+
+CREATE TABLE IF NOT EXISTS project_templates (
+    id          SERIAL PRIMARY KEY,
+    name        TEXT UNIQUE NOT NULL,
+    description TEXT,
+    created_at  TIMESTAMPTZ DEFAULT now()
+);
+
+CREATE TABLE IF NOT EXISTS template_categories (
+    id          SERIAL PRIMARY KEY,
+    template_id INT NOT NULL REFERENCES project_templates(id) ON DELETE CASCADE,
+    name        TEXT NOT NULL,
+    sort_order  INT NOT NULL DEFAULT 0,
+    UNIQUE(template_id, name)
+);
+
+CREATE TABLE IF NOT EXISTS template_items (
+    id                   SERIAL PRIMARY KEY,
+    template_category_id INT NOT NULL REFERENCES template_categories(id) ON DELETE CASCADE,
+    parent_id            INT REFERENCES template_items(id) ON DELETE CASCADE,
+    name                 TEXT NOT NULL,
+    sort_order           INT NOT NULL DEFAULT 0
+);
+
+CREATE INDEX IF NOT EXISTS idx_template_categories_template_id ON template_categories(template_id);
+CREATE INDEX IF NOT EXISTS idx_template_items_category_id ON template_items(template_category_id);
+
+-- ─── Built-in templates per procurement type ───
+
+INSERT INTO project_templates (name, description) VALUES
+    ('Lucrări',     'Contracte de execuție lucrări'),
+    ('Servicii',    'Contracte de prestări servicii'),
+    ('Produse',     'Contracte de furnizare produse'),
+    ('Acord-cadru', 'Acorduri-cadru cu contracte subsecvente')
+ON CONFLICT (name) DO NOTHING;
+
+INSERT INTO template_categories (template_id, name, sort_order)
+SELECT t.id, c.name, c.ord
+FROM project_templates t
+CROSS JOIN (VALUES
+    ('Eligibilitate', 0),
+    ('Financiar',     1),
+    ('Tehnic',        2),
+    ('PTE/PCCVI',     3)
+) AS c(name, ord)
+WHERE t.name IN ('Lucrări', 'Servicii', 'Produse', 'Acord-cadru')
+ON CONFLICT (template_id, name) DO NOTHING;
+
+INSERT INTO template_items (template_category_id, name, sort_order)
+SELECT tc.id, v.item, v.ord
+FROM (VALUES
+    ('Lucrări', 'Eligibilitate', 'Garantia de participare', 0),
+    ('Lucrări', 'Eligibilitate', 'Acorduri de subcontractare', 1),
+    ('Lucrări', 'Eligibilitate', 'Împuterniciri', 2),
+    ('Lucrări', 'Eligibilitate', 'Declarație privind conflictul de interese', 3),
+    ('Lucrări', 'Eligibilitate', 'Centralizator experienta similara', 4),
+    ('Lucrări', 'Eligibilitate', 'Personal', 5),
+    ('Lucrări', 'Eligibilitate', 'Utilaje și echipamente', 6),
+    ('Lucrări', 'Eligibilitate', 'Certificate ISO 9001 / 14001 / 45001', 7),
+    ('Lucrări', 'Financiar', 'Propunere financiara', 0),
+    ('Lucrări', 'Financiar', 'Liste de cantități', 1),
+    ('Lucrări', 'Financiar', 'Grafic de plăți', 2),
+    ('Lucrări', 'Tehnic', 'Grafic de execuție', 0),
+    ('Lucrări', 'Tehnic', 'Program de asigurare a calității', 1),
+    ('Lucrări', 'Tehnic', 'Plan de securitate și sănătate în muncă', 2),
+    ('Lucrări', 'PTE/PCCVI', 'PTE/PCCVI', 0),
+
+    ('Servicii', 'Eligibilitate', 'Garantia de participare', 0),
+    ('Servicii', 'Eligibilitate', 'Acorduri de subcontractare', 1),
+    ('Servicii', 'Eligibilitate', 'Împuterniciri', 2),
+    ('Servicii', 'Eligibilitate', 'Declarație privind conflictul de interese', 3),
+    ('Servicii', 'Eligibilitate', 'Centralizator experienta similara', 4),
+    ('Servicii', 'Eligibilitate', 'Personal', 5),
+    ('Servicii', 'Financiar', 'Propunere financiara', 0),
+    ('Servicii', 'Tehnic', 'Metodologie de prestare', 0),
+    ('Servicii', 'Tehnic', 'Organizarea echipei și alocarea resurselor', 1),
+    ('Servicii', 'Tehnic', 'Grafic de prestare', 2),
+    ('Servicii', 'PTE/PCCVI', 'PTE/PCCVI', 0),
+
+    ('Produse', 'Eligibilitate', 'Garantia de participare', 0),
+    ('Produse', 'Eligibilitate', 'Împuterniciri', 1),
+    ('Produse', 'Eligibilitate', 'Declarație privind conflictul de interese', 2),
+    ('Produse', 'Eligibilitate', 'Centralizator experienta similara', 3),
+    ('Produse', 'Eligibilitate', 'Autorizare din partea producătorului', 4),
+    ('Produse', 'Financiar', 'Propunere financiara', 0),
+    ('Produse', 'Tehnic', 'Fișe tehnice ale produselor', 0),
+    ('Produse', 'Tehnic', 'Certificate de conformitate', 1),
+    ('Produse', 'Tehnic', 'Termen de livrare și garanție', 2),
+    ('Produse', 'PTE/PCCVI', 'PTE/PCCVI', 0),
+
+    ('Acord-cadru', 'Eligibilitate', 'Garantia de participare', 0),
+    ('Acord-cadru', 'Eligibilitate', 'Acorduri de subcontractare', 1),
+    ('Acord-cadru', 'Eligibilitate', 'Împuterniciri', 2),
+    ('Acord-cadru', 'Eligibilitate', 'Declarație privind conflictul de interese', 3),
+    ('Acord-cadru', 'Eligibilitate', 'Centralizator experienta similara', 4),
+    ('Acord-cadru', 'Eligibilitate', 'Personal', 5),
+    ('Acord-cadru', 'Financiar', 'Propunere financiara', 0),
+    ('Acord-cadru', 'Financiar', 'Prețuri unitare pentru contractele subsecvente', 1),
+    ('Acord-cadru', 'Tehnic', 'Propunere tehnică pentru acordul-cadru', 0),
+    ('Acord-cadru', 'Tehnic', 'Capacitate pentru contracte subsecvente simultane', 1),
+    ('Acord-cadru', 'PTE/PCCVI', 'PTE/PCCVI', 0)
+) AS v(template, category, item, ord)
+JOIN project_templates t ON t.name = v.template
+JOIN template_categories tc ON tc.template_id = t.id AND tc.name = v.category;
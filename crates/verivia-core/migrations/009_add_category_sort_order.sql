@@ -0,0 +1,24 @@
+-- Categories are no longer fixed to the four defaults: projects can add, rename,
+-- delete and reorder their own. sort_order drives the display order.
+ALTER TABLE categories ADD COLUMN IF NOT EXISTS sort_order INT NOT NULL DEFAULT 0;
+
+-- Backfill: keep the historical order (Eligibilitate, Financiar, Tehnic, PTE/PCCVI),
+-- anything else after them in creation order.
+UPDATE categories c
+SET sort_order = ordered.rn - 1
+FROM (
+    SELECT id,
+           ROW_NUMBER() OVER (
+               PARTITION BY project_id
+               ORDER BY CASE LOWER(name)
+                            WHEN 'eligibilitate' THEN 0
+                            WHEN 'financiar'     THEN 1
+                            WHEN 'tehnic'        THEN 2
+                            WHEN 'pte/pccvi'     THEN 3
+                            ELSE 4
+                        END,
+                        id
+           ) AS rn
+    FROM categories
+) ordered
+WHERE c.id = ordered.id;
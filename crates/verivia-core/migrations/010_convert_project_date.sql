@@ -0,0 +1,52 @@
+-- projects.date was TEXT in MM.DD.YYYY and sorting relied on zero-padding.
+-- Convert it to a real DATE. Rows whose legacy value cannot be parsed are recorded
+-- in project_date_issues and fall back to their creation date until someone fixes them.
+
+CREATE TABLE IF NOT EXISTS project_date_issues (
+    id            SERIAL PRIMARY KEY,
+    project_id    INT NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    legacy_date   TEXT NOT NULL,
+    reason        TEXT NOT NULL,
+    fallback_date DATE NOT NULL,
+    recorded_at   TIMESTAMPTZ DEFAULT now()
+);
+
+-- Strict MM.DD.YYYY (zero-padding optional). NULL on bad format or impossible dates.
+CREATE OR REPLACE FUNCTION verivia_parse_legacy_date(s TEXT) RETURNS DATE AS $$
+DECLARE
+    parts TEXT[];
+BEGIN
+    parts := regexp_match(btrim(s), '^(\d{1,2})\.(\d{1,2})\.(\d{4})$');
+    IF parts IS NULL THEN
+        RETURN NULL;
+    END IF;
+    RETURN make_date(parts[3]::int, parts[1]::int, parts[2]::int);
+EXCEPTION WHEN others THEN
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql IMMUTABLE;
+
+ALTER TABLE projects ADD COLUMN date_parsed DATE;
+
+UPDATE projects SET date_parsed = verivia_parse_legacy_date(date);
+
+INSERT INTO project_date_issues (project_id, legacy_date, reason, fallback_date)
+SELECT id,
+       date,
+       CASE
+           WHEN btrim(date) ~ '^\d{1,2}\.\d{1,2}\.\d{4}$' THEN 'invalid calendar date'
+           ELSE 'unrecognized format (expected MM.DD.YYYY)'
+       END,
+       COALESCE(created_at, now())::date
+FROM projects
+WHERE date_parsed IS NULL;
+
+UPDATE projects SET date_parsed = COALESCE(created_at, now())::date WHERE date_parsed IS NULL;
+
+ALTER TABLE projects DROP COLUMN date;
+ALTER TABLE projects RENAME COLUMN date_parsed TO date;
+ALTER TABLE projects ALTER COLUMN date SET NOT NULL;
+
+DROP FUNCTION verivia_parse_legacy_date(TEXT);
+
+CREATE INDEX IF NOT EXISTS idx_project_date_issues_project_id ON project_date_issues(project_id);
@@ -0,0 +1,6 @@
+-- Procurement timeline per project. All optional: older projects have none.
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS submission_deadline    TIMESTAMPTZ;
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS clarification_deadline TIMESTAMPTZ;
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS opening_date           TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_projects_submission_deadline ON projects(submission_deadline);
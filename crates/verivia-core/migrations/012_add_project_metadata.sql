@@ -0,0 +1,12 @@
+-- Structured procurement metadata (previously only title/date/year/path).
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS contracting_authority TEXT;
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS notice_number         TEXT;          -- SEAP/SICAP, e.g. CN1045678
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS cpv_codes             TEXT[] NOT NULL DEFAULT '{}';  -- e.g. 45233120-6
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS estimated_value       NUMERIC(18, 2);
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS currency              TEXT;          -- ISO 4217, e.g. RON
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS procedure_type        TEXT;
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS lots                  INT[] NOT NULL DEFAULT '{}';
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS owner                 TEXT;          -- internal owner (user email)
+
+CREATE INDEX IF NOT EXISTS idx_projects_notice_number ON projects(notice_number);
+CREATE INDEX IF NOT EXISTS idx_projects_cpv_codes ON projects USING GIN (cpv_codes);
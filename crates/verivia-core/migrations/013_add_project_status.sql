@@ -0,0 +1,18 @@
+-- Project lifecycle: in_preparation → submitted → won / lost, with cancelled and under_appeal
+-- branches. Allowed transitions are enforced in status.rs; every change is logged.
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS status TEXT NOT NULL DEFAULT 'in_preparation'
+    CHECK (status IN ('in_preparation', 'submitted', 'won', 'lost', 'cancelled', 'under_appeal'));
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS status_changed_at TIMESTAMPTZ DEFAULT now();
+
+CREATE TABLE IF NOT EXISTS project_status_history (
+    id              SERIAL PRIMARY KEY,
+    project_id      INT NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    from_status     TEXT NOT NULL,
+    to_status       TEXT NOT NULL,
+    changed_by      TEXT NOT NULL,
+    changed_at      TIMESTAMPTZ NOT NULL DEFAULT now(),
+    override_reason TEXT            -- set when submitted with incomplete categories
+);
+
+CREATE INDEX IF NOT EXISTS idx_projects_status ON projects(status);
+CREATE INDEX IF NOT EXISTS idx_project_status_history_project_id ON project_status_history(project_id);
@@ -0,0 +1,25 @@
+-- Full-text search over project titles, checklist item names and notes.
+-- `romanian_unaccent` = Romanian stemming after stripping diacritics,
+-- so "garantie" finds "Garanția" and "imputerniciri" finds "Împuterniciri".
+CREATE EXTENSION IF NOT EXISTS unaccent;
+
+DO $$
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM pg_ts_config WHERE cfgname = 'romanian_unaccent') THEN
+        CREATE TEXT SEARCH CONFIGURATION romanian_unaccent (COPY = romanian);
+        ALTER TEXT SEARCH CONFIGURATION romanian_unaccent
+            ALTER MAPPING FOR hword, hword_part, word WITH unaccent, romanian_stem;
+    END IF;
+END
+$$;
+
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS search_vector tsvector
+    GENERATED ALWAYS AS (to_tsvector('romanian_unaccent', title)) STORED;
+ALTER TABLE checklist_items ADD COLUMN IF NOT EXISTS search_vector tsvector
+    GENERATED ALWAYS AS (to_tsvector('romanian_unaccent', name)) STORED;
+ALTER TABLE notes ADD COLUMN IF NOT EXISTS search_vector tsvector
+    GENERATED ALWAYS AS (to_tsvector('romanian_unaccent', text)) STORED;
+
+CREATE INDEX IF NOT EXISTS idx_projects_search ON projects USING GIN (search_vector);
+CREATE INDEX IF NOT EXISTS idx_checklist_items_search ON checklist_items USING GIN (search_vector);
+CREATE INDEX IF NOT EXISTS idx_notes_search ON notes USING GIN (search_vector);
@@ -0,0 +1,4 @@
+-- When each flag was set, for proposal → verification lead times in stats.rs.
+-- Items flagged before this migration keep NULL and are left out of the averages.
+ALTER TABLE checklist_items ADD COLUMN IF NOT EXISTS proposed_at TIMESTAMPTZ;
+ALTER TABLE checklist_items ADD COLUMN IF NOT EXISTS verified_at TIMESTAMPTZ;
@@ -0,0 +1,10 @@
+-- Soft delete: rows with deleted_at set are in the trash and hidden from every query.
+-- Deleting an item stamps its whole subtree with the same deleted_at, so restore can
+-- bring the subtree back together. trash::purge_trash removes rows past retention.
+ALTER TABLE projects        ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+ALTER TABLE checklist_items ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+ALTER TABLE notes           ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_projects_deleted_at ON projects(deleted_at) WHERE deleted_at IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_checklist_items_deleted_at ON checklist_items(deleted_at) WHERE deleted_at IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_notes_deleted_at ON notes(deleted_at) WHERE deleted_at IS NOT NULL;
@@ -0,0 +1,9 @@
+-- Years exported to an archive file (archive.rs). A listed year is read-only;
+-- with removed = TRUE its projects were also deleted and only the file holds them.
+CREATE TABLE IF NOT EXISTS archived_years (
+    year        TEXT PRIMARY KEY,
+    archived_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    file_path   TEXT NOT NULL,
+    checksum    TEXT NOT NULL,        -- SHA-256 of the archived data
+    removed     BOOLEAN NOT NULL DEFAULT FALSE
+);
@@ -0,0 +1,21 @@
+-- Read-only locks on a whole project or a single category (locks.rs). A lock blocks every
+-- checklist and note mutation below it; only admins can unlock, with a recorded reason.
+-- Admins are granted directly in the database: UPDATE users SET is_admin = TRUE WHERE email = '...';
+ALTER TABLE users ADD COLUMN IF NOT EXISTS is_admin BOOLEAN NOT NULL DEFAULT FALSE;
+
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS locked_at TIMESTAMPTZ;
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS locked_by TEXT;
+ALTER TABLE categories ADD COLUMN IF NOT EXISTS locked_at TIMESTAMPTZ;
+ALTER TABLE categories ADD COLUMN IF NOT EXISTS locked_by TEXT;
+
+CREATE TABLE IF NOT EXISTS lock_history (
+    id          SERIAL PRIMARY KEY,
+    project_id  INT NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    category_id INT REFERENCES categories(id) ON DELETE CASCADE,   -- NULL = whole project
+    action      TEXT NOT NULL CHECK (action IN ('lock', 'unlock')),
+    changed_by  TEXT NOT NULL,
+    changed_at  TIMESTAMPTZ NOT NULL DEFAULT now(),
+    reason      TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_lock_history_project_id ON lock_history(project_id);
@@ -0,0 +1,19 @@
+-- Named Excel import layouts (import_profiles.rs). Rows, columns and sheet indexes are
+-- 1-based, as shown in the spreadsheet. header_row NULL = detect the header automatically.
+CREATE TABLE IF NOT EXISTS import_profiles (
+    id                 SERIAL PRIMARY KEY,
+    name               TEXT UNIQUE NOT NULL,
+    sheet_name         TEXT,                        -- takes precedence over sheet_index
+    sheet_index        INT NOT NULL DEFAULT 1 CHECK (sheet_index >= 1),
+    header_row         INT CHECK (header_row >= 1),
+    name_column        INT NOT NULL DEFAULT 2 CHECK (name_column >= 1),
+    description_column INT CHECK (description_column >= 1),
+    reference_column   INT CHECK (reference_column >= 1),
+    end_marker         TEXT,                        -- stop at the first row starting with it
+    created_at         TIMESTAMPTZ DEFAULT now()
+);
+
+-- The layout the importer always used: first sheet, five header rows, requirements in column B
+INSERT INTO import_profiles (name, sheet_index, header_row, name_column)
+VALUES ('Implicit', 1, 5, 2)
+ON CONFLICT (name) DO NOTHING;
@@ -0,0 +1,3 @@
+-- Original numbering of an imported requirement ("1.2", "b)", "iv."), kept apart from its name
+-- so re-imports can match items by reference (excel/outline.rs).
+ALTER TABLE checklist_items ADD COLUMN IF NOT EXISTS reference TEXT;
@@ -0,0 +1,4 @@
+-- Requirements whose text changed when the specification was re-imported (reimport.rs):
+-- their verification is cleared and they stay flagged, with the former text, until verified again.
+ALTER TABLE checklist_items ADD COLUMN IF NOT EXISTS needs_review BOOLEAN NOT NULL DEFAULT false;
+ALTER TABLE checklist_items ADD COLUMN IF NOT EXISTS previous_name TEXT;
@@ -0,0 +1,9 @@
+-- Our offer's answer to a requirement ("Conform – vezi pag. 34 din PT") and where the
+-- supporting document is, for the technical compliance matrix (export.rs).
+ALTER TABLE checklist_items ADD COLUMN IF NOT EXISTS response TEXT;
+ALTER TABLE checklist_items ADD COLUMN IF NOT EXISTS document_reference TEXT;
+
+-- Columns of the source spreadsheet that already hold them (excel/mod.rs)
+ALTER TABLE import_profiles ADD COLUMN IF NOT EXISTS response_column INT CHECK (response_column >= 1);
+ALTER TABLE import_profiles ADD COLUMN IF NOT EXISTS document_reference_column INT
+    CHECK (document_reference_column >= 1);
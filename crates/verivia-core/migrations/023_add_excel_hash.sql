@@ -0,0 +1,3 @@
+-- SHA-256 of the linked spreadsheet when its requirements were last imported (linked_excel.rs).
+-- NULL for files linked before hashing: the first check records the current content.
+ALTER TABLE categories ADD COLUMN IF NOT EXISTS excel_hash TEXT;
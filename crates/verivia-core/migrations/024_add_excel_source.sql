@@ -0,0 +1,5 @@
+-- Sheet and import profile the linked spreadsheet was imported with, so change checks
+-- (linked_excel.rs) parse it the same way. NULL: the detected sheet and layout.
+ALTER TABLE categories ADD COLUMN IF NOT EXISTS excel_sheet TEXT;
+ALTER TABLE categories ADD COLUMN IF NOT EXISTS excel_profile_id INT
+    REFERENCES import_profiles(id) ON DELETE SET NULL;
@@ -0,0 +1,11 @@
+-- Categories are looked up by LOWER(name) (checklist.rs), so names must be unique per
+-- project regardless of case. Existing case variants keep the oldest one as is; the
+-- others get a " (2)", " (3)", ... suffix.
+UPDATE categories c SET name = c.name || ' (' || d.n || ')'
+FROM (
+    SELECT id, ROW_NUMBER() OVER (PARTITION BY project_id, LOWER(name) ORDER BY id) AS n
+    FROM categories
+) d
+WHERE d.id = c.id AND d.n > 1;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_categories_project_lower_name ON categories(project_id, LOWER(name));
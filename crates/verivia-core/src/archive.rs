@@ -0,0 +1,656 @@
+//! Year archives: a whole year (projects, categories, items, notes, status and lock history,
+//! date issues) exported to a gzip-compressed JSON file that can be restored on its own.
+
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+
+use crate::errors::{AppError, Result};
+use crate::guard;
+use crate::models::{ArchiveCounts, ArchiveMode, ArchiveReport, ArchivedYear};
+use crate::tree::parents_first;
+
+const ARCHIVE_FORMAT: &str = "verivia-year-archive";
+const ARCHIVE_VERSION: u32 = 1;
+
+// ─────────────────────────── File Layout ───────────────────────────
+// Rows keep their database ids so a restore rebuilds every reference unchanged.
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ArchiveFile {
+    format: String,
+    version: u32,
+    year: String,
+    created_at: DateTime<Utc>,
+    /// SHA-256 of `data` serialized as JSON
+    checksum: String,
+    data: ArchiveData,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ArchiveData {
+    projects: Vec<ProjectRow>,
+    categories: Vec<CategoryRow>,
+    items: Vec<ItemRow>,
+    notes: Vec<NoteRow>,
+    status_history: Vec<StatusRow>,
+    date_issues: Vec<DateIssueRow>,
+    #[serde(default)]
+    lock_history: Vec<LockRow>,
+}
+
+impl ArchiveData {
+    fn counts(&self) -> ArchiveCounts {
+        ArchiveCounts {
+            projects: self.projects.len(),
+            categories: self.categories.len(),
+            items: self.items.len(),
+            notes: self.notes.len(),
+            status_changes: self.status_history.len(),
+            date_issues: self.date_issues.len(),
+            lock_changes: self.lock_history.len(),
+        }
+    }
+
+    fn checksum(&self) -> Result<String> {
+        let json = serde_json::to_vec(self).map_err(|e| AppError::Archive(e.to_string()))?;
+        Ok(format!("{:x}", Sha256::digest(&json)))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProjectRow {
+    id: i32,
+    title: String,
+    year: String,
+    date: NaiveDate,
+    path: Option<String>,
+    created_at: Option<DateTime<Utc>>,
+    submission_deadline: Option<DateTime<Utc>>,
+    clarification_deadline: Option<DateTime<Utc>>,
+    opening_date: Option<DateTime<Utc>>,
+    contracting_authority: Option<String>,
+    notice_number: Option<String>,
+    cpv_codes: Vec<String>,
+    estimated_value: Option<Decimal>,
+    currency: Option<String>,
+    procedure_type: Option<String>,
+    lots: Vec<i32>,
+    owner: Option<String>,
+    status: String,
+    status_changed_at: Option<DateTime<Utc>>,
+    deleted_at: Option<DateTime<Utc>>,
+    locked_at: Option<DateTime<Utc>>,
+    locked_by: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CategoryRow {
+    id: i32,
+    project_id: i32,
+    name: String,
+    excel_path: Option<String>,
+    sort_order: i32,
+    locked_at: Option<DateTime<Utc>>,
+    locked_by: Option<String>,
+    excel_hash: Option<String>,
+    excel_sheet: Option<String>,
+    excel_profile_id: Option<i32>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ItemRow {
+    id: i32,
+    category_id: i32,
+    parent_id: Option<i32>,
+    name: String,
+    proposed: bool,
+    verified: bool,
+    sort_order: i32,
+    proposed_by: Option<String>,
+    verified_by: Option<String>,
+    proposed_at: Option<DateTime<Utc>>,
+    verified_at: Option<DateTime<Utc>>,
+    deleted_at: Option<DateTime<Utc>>,
+    reference: Option<String>,
+    #[serde(default)]
+    needs_review: bool,
+    previous_name: Option<String>,
+    response: Option<String>,
+    document_reference: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NoteRow {
+    id: i32,
+    item_id: i32,
+    user: String,
+    date: String,
+    text: String,
+    deleted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StatusRow {
+    id: i32,
+    project_id: i32,
+    from_status: String,
+    to_status: String,
+    changed_by: String,
+    changed_at: DateTime<Utc>,
+    override_reason: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LockRow {
+    id: i32,
+    project_id: i32,
+    category_id: Option<i32>,
+    action: String,
+    changed_by: String,
+    changed_at: DateTime<Utc>,
+    reason: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DateIssueRow {
+    id: i32,
+    project_id: i32,
+    legacy_date: String,
+    reason: String,
+    fallback_date: NaiveDate,
+    recorded_at: Option<DateTime<Utc>>,
+}
+
+// ─────────────────────────── Archive ───────────────────────────
+
+/// Export `year` to `path`, read the file back to verify it, then either keep the year
+/// as read-only or remove its projects from the database.
+/// The year is read-only from the start (once its pending writes are committed, see
+/// `guard`), so nothing changes while it is being exported.
+pub async fn archive_year(
+    pool: &PgPool,
+    year: &str,
+    path: &Path,
+    mode: ArchiveMode,
+) -> Result<ArchiveReport> {
+    let has_projects = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM projects WHERE year = $1) AS "v!""#,
+        year
+    )
+    .fetch_one(pool)
+    .await?;
+    if !has_projects {
+        return Err(AppError::NotFound(format!("Year {} has no projects", year)));
+    }
+
+    let path_str = path.to_string_lossy().into_owned();
+    let mut tx = pool.begin().await?;
+    guard::lock_year_exclusive(&mut tx, year).await?;
+    let inserted = sqlx::query!(
+        "INSERT INTO archived_years (year, file_path, checksum) VALUES ($1, $2, '')
+         ON CONFLICT (year) DO NOTHING",
+        year, path_str
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+    if inserted == 0 {
+        return Err(AppError::Validation(format!("Anul {} este deja arhivat", year)));
+    }
+    tx.commit().await?;
+
+    match export_and_verify(pool, year, path).await {
+        Ok((checksum, counts)) => {
+            let mut tx = pool.begin().await?;
+            let removed = mode == ArchiveMode::Remove;
+            if removed {
+                // CASCADE removes categories, items, notes, status history and date issues
+                sqlx::query!("DELETE FROM projects WHERE year = $1", year)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            sqlx::query!(
+                "UPDATE archived_years SET checksum = $1, removed = $2 WHERE year = $3",
+                checksum, removed, year
+            )
+            .execute(&mut *tx)
+            .await?;
+            tx.commit().await?;
+
+            Ok(ArchiveReport {
+                year: year.to_string(),
+                path: path_str,
+                checksum,
+                counts,
+                removed,
+            })
+        }
+        Err(e) => {
+            sqlx::query!("DELETE FROM archived_years WHERE year = $1", year)
+                .execute(pool)
+                .await?;
+            Err(e)
+        }
+    }
+}
+
+async fn export_and_verify(pool: &PgPool, year: &str, path: &Path) -> Result<(String, ArchiveCounts)> {
+    let data = load_year(pool, year).await?;
+    let counts = data.counts();
+    let file = ArchiveFile {
+        format: ARCHIVE_FORMAT.to_string(),
+        version: ARCHIVE_VERSION,
+        year: year.to_string(),
+        created_at: Utc::now(),
+        checksum: data.checksum()?,
+        data,
+    };
+
+    // Written next to the target and renamed into place once verified, so a failed
+    // export leaves no partial archive behind (nor replaces an existing file)
+    let part = part_path(path);
+    let written = write_verified(&part, &file).and_then(|()| std::fs::rename(&part, path).map_err(io_error));
+    if written.is_err() {
+        let _ = std::fs::remove_file(&part);
+    }
+    written?;
+
+    Ok((file.checksum, counts))
+}
+
+fn write_verified(path: &Path, file: &ArchiveFile) -> Result<()> {
+    let json = serde_json::to_vec(file).map_err(|e| AppError::Archive(e.to_string()))?;
+    let mut encoder = GzEncoder::new(File::create(path).map_err(io_error)?, Compression::best());
+    encoder.write_all(&json).map_err(io_error)?;
+    encoder.finish().map_err(io_error)?.sync_all().map_err(io_error)?;
+
+    let written = read_archive(path)?;
+    if written.year != file.year || written.checksum != file.checksum || written.data.counts() != file.data.counts() {
+        return Err(AppError::Archive(format!(
+            "Verification of {} failed: the file does not match the exported data",
+            path.display()
+        )));
+    }
+    Ok(())
+}
+
+/// "<file>.part" next to `path`.
+fn part_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".part");
+    path.with_file_name(name)
+}
+
+/// Every row of the year, read from one consistent snapshot.
+async fn load_year(pool: &PgPool, year: &str) -> Result<ArchiveData> {
+    let mut tx = pool.begin().await?;
+    sqlx::query!("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ READ ONLY")
+        .execute(&mut *tx)
+        .await?;
+
+    let projects = sqlx::query_as!(
+        ProjectRow,
+        "SELECT id, title, year, date, path, created_at, submission_deadline, clarification_deadline,
+                opening_date, contracting_authority, notice_number, cpv_codes, estimated_value,
+                currency, procedure_type, lots, owner, status, status_changed_at, deleted_at,
+                locked_at, locked_by
+         FROM projects WHERE year = $1 ORDER BY id",
+        year
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let categories = sqlx::query_as!(
+        CategoryRow,
+        "SELECT c.id, c.project_id, c.name, c.excel_path, c.sort_order, c.locked_at, c.locked_by, c.excel_hash,
+                c.excel_sheet, c.excel_profile_id
+         FROM categories c JOIN projects p ON p.id = c.project_id
+         WHERE p.year = $1 ORDER BY c.id",
+        year
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let items = sqlx::query_as!(
+        ItemRow,
+        "SELECT i.id, i.category_id, i.parent_id, i.name, i.proposed, i.verified, i.sort_order,
+                i.proposed_by, i.verified_by, i.proposed_at, i.verified_at, i.deleted_at, i.reference,
+                i.needs_review, i.previous_name, i.response, i.document_reference
+         FROM checklist_items i
+         JOIN categories c ON c.id = i.category_id
+         JOIN projects p ON p.id = c.project_id
+         WHERE p.year = $1 ORDER BY i.id",
+        year
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let notes = sqlx::query_as!(
+        NoteRow,
+        r#"SELECT n.id, n.item_id, n."user", n.date, n.text, n.deleted_at
+           FROM notes n
+           JOIN checklist_items i ON i.id = n.item_id
+           JOIN categories c ON c.id = i.category_id
+           JOIN projects p ON p.id = c.project_id
+           WHERE p.year = $1 ORDER BY n.id"#,
+        year
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let status_history = sqlx::query_as!(
+        StatusRow,
+        "SELECT h.id, h.project_id, h.from_status, h.to_status, h.changed_by, h.changed_at, h.override_reason
+         FROM project_status_history h JOIN projects p ON p.id = h.project_id
+         WHERE p.year = $1 ORDER BY h.id",
+        year
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let date_issues = sqlx::query_as!(
+        DateIssueRow,
+        "SELECT d.id, d.project_id, d.legacy_date, d.reason, d.fallback_date, d.recorded_at
+         FROM project_date_issues d JOIN projects p ON p.id = d.project_id
+         WHERE p.year = $1 ORDER BY d.id",
+        year
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let lock_history = sqlx::query_as!(
+        LockRow,
+        "SELECT h.id, h.project_id, h.category_id, h.action, h.changed_by, h.changed_at, h.reason
+         FROM lock_history h JOIN projects p ON p.id = h.project_id
+         WHERE p.year = $1 ORDER BY h.id",
+        year
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(ArchiveData {
+        projects,
+        categories,
+        items,
+        notes,
+        status_history,
+        date_issues,
+        lock_history,
+    })
+}
+
+/// Decompress and parse an archive file, rejecting it if the checksum does not match.
+fn read_archive(path: &Path) -> Result<ArchiveFile> {
+    let mut json = Vec::new();
+    GzDecoder::new(File::open(path).map_err(io_error)?)
+        .read_to_end(&mut json)
+        .map_err(io_error)?;
+
+    let file: ArchiveFile = serde_json::from_slice(&json)
+        .map_err(|e| AppError::Archive(format!("{} is not a year archive: {}", path.display(), e)))?;
+
+    if file.format != ARCHIVE_FORMAT || file.version > ARCHIVE_VERSION {
+        return Err(AppError::Archive(format!(
+            "Unsupported archive format {} v{}",
+            file.format, file.version
+        )));
+    }
+    if file.data.checksum()? != file.checksum {
+        return Err(AppError::Archive(format!("{} is corrupted (checksum mismatch)", path.display())));
+    }
+    Ok(file)
+}
+
+fn io_error(e: std::io::Error) -> AppError {
+    AppError::Archive(e.to_string())
+}
+
+// ─────────────────────────── Restore ───────────────────────────
+
+/// Bring an archived year back. A read-only year just becomes writable again
+/// (after the file is verified); a removed year is re-inserted with its original ids.
+/// The file must be the one recorded when the year was archived (same checksum), so an
+/// older or edited export cannot replace the year.
+pub async fn restore_year(pool: &PgPool, path: &Path) -> Result<ArchiveReport> {
+    let file = read_archive(path)?;
+    let year = file.year.as_str();
+    let data = &file.data;
+
+    let mut tx = pool.begin().await?;
+
+    let archived = sqlx::query!(
+        "SELECT removed, checksum FROM archived_years WHERE year = $1 FOR UPDATE",
+        year
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let removed = match archived {
+        Some(archived) if archived.checksum != file.checksum => {
+            return Err(AppError::Validation(format!(
+                "Fișierul nu este arhiva înregistrată pentru anul {} (sumă de control diferită)",
+                year
+            )));
+        }
+        Some(archived) => archived.removed,
+        // Not listed at all: restoring into another database
+        None => true,
+    };
+
+    if removed {
+        let project_ids: Vec<i32> = data.projects.iter().map(|p| p.id).collect();
+        let clash = sqlx::query_scalar!(
+            r#"SELECT EXISTS(SELECT 1 FROM projects WHERE id = ANY($1) OR year = $2) AS "v!""#,
+            &project_ids,
+            year
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        if clash {
+            return Err(AppError::Validation(format!(
+                "Anul {} are deja proiecte în baza de date",
+                year
+            )));
+        }
+
+        insert_year(&mut tx, data).await?;
+    }
+
+    sqlx::query!("DELETE FROM archived_years WHERE year = $1", year)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(ArchiveReport {
+        year: file.year.clone(),
+        path: path.to_string_lossy().into_owned(),
+        checksum: file.checksum.clone(),
+        counts: data.counts(),
+        removed: false,
+    })
+}
+
+async fn insert_year(tx: &mut sqlx::Transaction<'_, sqlx::Postgres>, data: &ArchiveData) -> Result<()> {
+    for p in &data.projects {
+        sqlx::query!(
+            "INSERT INTO projects (id, title, year, date, path, created_at, submission_deadline,
+                 clarification_deadline, opening_date, contracting_authority, notice_number, cpv_codes,
+                 estimated_value, currency, procedure_type, lots, owner, status, status_changed_at, deleted_at,
+                 locked_at, locked_by)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20,
+                     $21, $22)",
+            p.id, p.title, p.year, p.date, p.path, p.created_at, p.submission_deadline,
+            p.clarification_deadline, p.opening_date, p.contracting_authority, p.notice_number,
+            &p.cpv_codes, p.estimated_value, p.currency, p.procedure_type, &p.lots, p.owner,
+            p.status, p.status_changed_at, p.deleted_at, p.locked_at, p.locked_by
+        )
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    let c = &data.categories;
+    // A profile deleted since the archive was made is dropped (the layout is detected again)
+    sqlx::query!(
+        "INSERT INTO categories (id, project_id, name, excel_path, sort_order, locked_at, locked_by, excel_hash,
+                                 excel_sheet, excel_profile_id)
+         SELECT u.id, u.project_id, u.name, u.excel_path, u.sort_order, u.locked_at, u.locked_by, u.excel_hash,
+                u.excel_sheet, (SELECT ip.id FROM import_profiles ip WHERE ip.id = u.excel_profile_id)
+         FROM UNNEST($1::int[], $2::int[], $3::text[], $4::text[], $5::int[], $6::timestamptz[], $7::text[],
+             $8::text[], $9::text[], $10::int[])
+             AS u(id, project_id, name, excel_path, sort_order, locked_at, locked_by, excel_hash,
+                  excel_sheet, excel_profile_id)",
+        &c.iter().map(|r| r.id).collect::<Vec<_>>(),
+        &c.iter().map(|r| r.project_id).collect::<Vec<_>>(),
+        &c.iter().map(|r| r.name.clone()).collect::<Vec<_>>(),
+        &c.iter().map(|r| r.excel_path.clone()).collect::<Vec<_>>() as &[Option<String>],
+        &c.iter().map(|r| r.sort_order).collect::<Vec<_>>(),
+        &c.iter().map(|r| r.locked_at).collect::<Vec<_>>() as &[Option<DateTime<Utc>>],
+        &c.iter().map(|r| r.locked_by.clone()).collect::<Vec<_>>() as &[Option<String>],
+        &c.iter().map(|r| r.excel_hash.clone()).collect::<Vec<_>>() as &[Option<String>],
+        &c.iter().map(|r| r.excel_sheet.clone()).collect::<Vec<_>>() as &[Option<String>],
+        &c.iter().map(|r| r.excel_profile_id).collect::<Vec<_>>() as &[Option<i32>]
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    // Parents first keeps the parent_id reference valid row by row
+    let items = parents_first(&data.items, |i| (i.id, i.parent_id));
+    sqlx::query!(
+        "INSERT INTO checklist_items (id, category_id, parent_id, name, proposed, verified, sort_order,
+             proposed_by, verified_by, proposed_at, verified_at, deleted_at, reference,
+             needs_review, previous_name, response, document_reference)
+         SELECT * FROM UNNEST($1::int[], $2::int[], $3::int[], $4::text[], $5::bool[], $6::bool[], $7::int[],
+             $8::text[], $9::text[], $10::timestamptz[], $11::timestamptz[], $12::timestamptz[], $13::text[],
+             $14::bool[], $15::text[], $16::text[], $17::text[])",
+        &items.iter().map(|r| r.id).collect::<Vec<_>>(),
+        &items.iter().map(|r| r.category_id).collect::<Vec<_>>(),
+        &items.iter().map(|r| r.parent_id).collect::<Vec<_>>() as &[Option<i32>],
+        &items.iter().map(|r| r.name.clone()).collect::<Vec<_>>(),
+        &items.iter().map(|r| r.proposed).collect::<Vec<_>>(),
+        &items.iter().map(|r| r.verified).collect::<Vec<_>>(),
+        &items.iter().map(|r| r.sort_order).collect::<Vec<_>>(),
+        &items.iter().map(|r| r.proposed_by.clone()).collect::<Vec<_>>() as &[Option<String>],
+        &items.iter().map(|r| r.verified_by.clone()).collect::<Vec<_>>() as &[Option<String>],
+        &items.iter().map(|r| r.proposed_at).collect::<Vec<_>>() as &[Option<DateTime<Utc>>],
+        &items.iter().map(|r| r.verified_at).collect::<Vec<_>>() as &[Option<DateTime<Utc>>],
+        &items.iter().map(|r| r.deleted_at).collect::<Vec<_>>() as &[Option<DateTime<Utc>>],
+        &items.iter().map(|r| r.reference.clone()).collect::<Vec<_>>() as &[Option<String>],
+        &items.iter().map(|r| r.needs_review).collect::<Vec<_>>(),
+        &items.iter().map(|r| r.previous_name.clone()).collect::<Vec<_>>() as &[Option<String>],
+        &items.iter().map(|r| r.response.clone()).collect::<Vec<_>>() as &[Option<String>],
+        &items.iter().map(|r| r.document_reference.clone()).collect::<Vec<_>>() as &[Option<String>]
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    let n = &data.notes;
+    sqlx::query!(
+        r#"INSERT INTO notes (id, item_id, "user", date, text, deleted_at)
+           SELECT * FROM UNNEST($1::int[], $2::int[], $3::text[], $4::text[], $5::text[], $6::timestamptz[])"#,
+        &n.iter().map(|r| r.id).collect::<Vec<_>>(),
+        &n.iter().map(|r| r.item_id).collect::<Vec<_>>(),
+        &n.iter().map(|r| r.user.clone()).collect::<Vec<_>>(),
+        &n.iter().map(|r| r.date.clone()).collect::<Vec<_>>(),
+        &n.iter().map(|r| r.text.clone()).collect::<Vec<_>>(),
+        &n.iter().map(|r| r.deleted_at).collect::<Vec<_>>() as &[Option<DateTime<Utc>>]
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    let h = &data.status_history;
+    sqlx::query!(
+        "INSERT INTO project_status_history (id, project_id, from_status, to_status, changed_by, changed_at, override_reason)
+         SELECT * FROM UNNEST($1::int[], $2::int[], $3::text[], $4::text[], $5::text[], $6::timestamptz[], $7::text[])",
+        &h.iter().map(|r| r.id).collect::<Vec<_>>(),
+        &h.iter().map(|r| r.project_id).collect::<Vec<_>>(),
+        &h.iter().map(|r| r.from_status.clone()).collect::<Vec<_>>(),
+        &h.iter().map(|r| r.to_status.clone()).collect::<Vec<_>>(),
+        &h.iter().map(|r| r.changed_by.clone()).collect::<Vec<_>>(),
+        &h.iter().map(|r| r.changed_at).collect::<Vec<_>>(),
+        &h.iter().map(|r| r.override_reason.clone()).collect::<Vec<_>>() as &[Option<String>]
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    let d = &data.date_issues;
+    sqlx::query!(
+        "INSERT INTO project_date_issues (id, project_id, legacy_date, reason, fallback_date, recorded_at)
+         SELECT * FROM UNNEST($1::int[], $2::int[], $3::text[], $4::text[], $5::date[], $6::timestamptz[])",
+        &d.iter().map(|r| r.id).collect::<Vec<_>>(),
+        &d.iter().map(|r| r.project_id).collect::<Vec<_>>(),
+        &d.iter().map(|r| r.legacy_date.clone()).collect::<Vec<_>>(),
+        &d.iter().map(|r| r.reason.clone()).collect::<Vec<_>>(),
+        &d.iter().map(|r| r.fallback_date).collect::<Vec<_>>(),
+        &d.iter().map(|r| r.recorded_at).collect::<Vec<_>>() as &[Option<DateTime<Utc>>]
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    let l = &data.lock_history;
+    sqlx::query!(
+        "INSERT INTO lock_history (id, project_id, category_id, action, changed_by, changed_at, reason)
+         SELECT * FROM UNNEST($1::int[], $2::int[], $3::int[], $4::text[], $5::text[], $6::timestamptz[], $7::text[])",
+        &l.iter().map(|r| r.id).collect::<Vec<_>>(),
+        &l.iter().map(|r| r.project_id).collect::<Vec<_>>(),
+        &l.iter().map(|r| r.category_id).collect::<Vec<_>>() as &[Option<i32>],
+        &l.iter().map(|r| r.action.clone()).collect::<Vec<_>>(),
+        &l.iter().map(|r| r.changed_by.clone()).collect::<Vec<_>>(),
+        &l.iter().map(|r| r.changed_at).collect::<Vec<_>>(),
+        &l.iter().map(|r| r.reason.clone()).collect::<Vec<_>>() as &[Option<String>]
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    // Explicit ids do not advance the sequences; make sure new rows never collide
+    sqlx::query!(
+        "SELECT
+             setval('projects_id_seq', GREATEST((SELECT MAX(id) FROM projects), (SELECT last_value FROM projects_id_seq))) AS projects,
+             setval('categories_id_seq', GREATEST((SELECT MAX(id) FROM categories), (SELECT last_value FROM categories_id_seq))) AS categories,
+             setval('checklist_items_id_seq', GREATEST((SELECT MAX(id) FROM checklist_items), (SELECT last_value FROM checklist_items_id_seq))) AS checklist_items,
+             setval('notes_id_seq', GREATEST((SELECT MAX(id) FROM notes), (SELECT last_value FROM notes_id_seq))) AS notes,
+             setval('project_status_history_id_seq',
+                    GREATEST((SELECT MAX(id) FROM project_status_history), (SELECT last_value FROM project_status_history_id_seq))) AS project_status_history,
+             setval('project_date_issues_id_seq',
+                    GREATEST((SELECT MAX(id) FROM project_date_issues), (SELECT last_value FROM project_date_issues_id_seq))) AS project_date_issues,
+             setval('lock_history_id_seq',
+                    GREATEST((SELECT MAX(id) FROM lock_history), (SELECT last_value FROM lock_history_id_seq))) AS lock_history"
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+// ─────────────────────────── List ───────────────────────────
+
+pub async fn list_archived_years(pool: &PgPool) -> Result<Vec<ArchivedYear>> {
+    let years = sqlx::query_as!(
+        ArchivedYear,
+        "SELECT year, archived_at, file_path, checksum, removed FROM archived_years ORDER BY year"
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(years)
+}
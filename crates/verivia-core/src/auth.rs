@@ -119,6 +119,48 @@ pub async fn register(
     Ok(())
 }
 
+// ─────────────────────────── Category Roles ───────────────────────────
+
+/// Grant (or update) a user's roles on a category. Role keys are stored lowercase,
+/// matching what the frontend looks up, so custom categories work like the defaults.
+pub async fn set_category_role(
+    pool: &PgPool,
+    user_id: i32,
+    category: &str,
+    flags: &RoleFlags,
+) -> Result<()> {
+    let category = category.trim().to_lowercase();
+    if category.is_empty() {
+        return Err(AppError::Validation("Numele categoriei nu poate fi gol".into()));
+    }
+
+    sqlx::query!(
+        "INSERT INTO user_roles (user_id, category, is_editor, is_verificator)
+         VALUES ($1, $2, $3, $4)
+         ON CONFLICT (user_id, category)
+         DO UPDATE SET is_editor = EXCLUDED.is_editor, is_verificator = EXCLUDED.is_verificator",
+        user_id,
+        category,
+        flags.editor,
+        flags.verificator
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn remove_category_role(pool: &PgPool, user_id: i32, category: &str) -> Result<()> {
+    sqlx::query!(
+        "DELETE FROM user_roles WHERE user_id = $1 AND category = $2",
+        user_id,
+        category.trim().to_lowercase()
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
 // ─────────────────────────── JWT Verification ───────────────────────────
 
 pub fn verify_token(token: &str) -> Result<JwtClaims> {
@@ -130,6 +172,15 @@ pub fn verify_token(token: &str) -> Result<JwtClaims> {
     Ok(data.claims)
 }
 
+/// The e-mail of `user_id` if it is an admin; granting roles and unlocking checklists
+/// are reserved to admins.
+pub async fn require_admin(pool: &PgPool, user_id: i32) -> Result<String> {
+    sqlx::query_scalar!("SELECT email FROM users WHERE id = $1 AND is_admin", user_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::Auth("Operație permisă doar administratorilor".into()))
+}
+
 // ─────────────────────────── Helpers ───────────────────────────
 
 async fn build_user_profile(pool: &PgPool, user_id: i32, email: &str) -> Result<UserProfile> {
@@ -153,9 +204,14 @@ async fn build_user_profile(pool: &PgPool, user_id: i32, email: &str) -> Result<
         );
     }
 
+    let is_admin = sqlx::query_scalar!("SELECT is_admin FROM users WHERE id = $1", user_id)
+        .fetch_one(pool)
+        .await?;
+
     Ok(UserProfile {
         id: user_id,
         email: email.to_string(),
         roles,
+        is_admin,
     })
 }
@@ -0,0 +1,287 @@
+use std::collections::HashMap;
+
+use sqlx::PgPool;
+
+use crate::errors::{AppError, Result};
+use crate::guard;
+use crate::models::{Category, ChecklistImportOptions, ChecklistImportPreview, ImportMode};
+use crate::tree::{copy_checklist_items, load_nested_checklist};
+
+// ─────────────────────────── Create ───────────────────────────
+
+/// Add a custom category to a project. It is appended after the existing ones.
+pub async fn create_category(pool: &PgPool, project_id: i32, name: &str) -> Result<Category> {
+    let name = validate_name(name)?;
+
+    let mut tx = pool.begin().await?;
+    guard::ensure_checklist_writable(&mut tx, project_id).await?;
+
+    let project_exists = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM projects WHERE id = $1 AND deleted_at IS NULL) AS "v!""#,
+        project_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if !project_exists {
+        return Err(AppError::NotFound(format!("Project id={} not found", project_id)));
+    }
+
+    let max_order: i32 = sqlx::query_scalar!(
+        "SELECT COALESCE(MAX(sort_order), -1) as \"v!\" FROM categories WHERE project_id = $1",
+        project_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    // ON CONFLICT covers both an existing name (in any case, see migration 025) and a
+    // concurrent insert of the same name
+    let category = sqlx::query_as!(
+        Category,
+        "INSERT INTO categories (project_id, name, sort_order) VALUES ($1, $2, $3)
+         ON CONFLICT DO NOTHING
+         RETURNING id, project_id, name, excel_path, sort_order",
+        project_id, name, max_order + 1
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| duplicate_name(name))?;
+
+    tx.commit().await?;
+    Ok(category)
+}
+
+// ─────────────────────────── Rename ───────────────────────────
+
+/// Rename a category. Roles held on the old name are also granted on the new one,
+/// so users keep access to the renamed checklist.
+pub async fn rename_category(pool: &PgPool, category_id: i32, new_name: &str) -> Result<()> {
+    let new_name = validate_name(new_name)?;
+
+    let mut tx = pool.begin().await?;
+    guard::ensure_category_writable(&mut tx, category_id).await?;
+
+    let old_name = sqlx::query_scalar!("SELECT name FROM categories WHERE id = $1", category_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Category id={} not found", category_id)))?;
+
+    let result = sqlx::query!(
+        "UPDATE categories SET name = $1 WHERE id = $2",
+        new_name, category_id
+    )
+    .execute(&mut *tx)
+    .await;
+
+    match result {
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            return Err(duplicate_name(new_name));
+        }
+        other => {
+            other?;
+        }
+    }
+
+    sqlx::query!(
+        "INSERT INTO user_roles (user_id, category, is_editor, is_verificator)
+         SELECT user_id, $2, is_editor, is_verificator FROM user_roles WHERE category = $1
+         ON CONFLICT (user_id, category) DO NOTHING",
+        old_name.to_lowercase(), new_name.to_lowercase()
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+// ─────────────────────────── Delete ───────────────────────────
+
+/// Delete an empty category. A category with live items is refused: its items go to the
+/// trash first (`checklist::delete_item`), where they can still be restored. Items already
+/// in the trash are removed with the category.
+pub async fn delete_category(pool: &PgPool, category_id: i32) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    guard::ensure_category_writable(&mut tx, category_id).await?;
+
+    let category = sqlx::query!(
+        r#"SELECT c.name,
+                  EXISTS(SELECT 1 FROM checklist_items i
+                         WHERE i.category_id = c.id AND i.deleted_at IS NULL) AS "has_items!"
+           FROM categories c WHERE c.id = $1 FOR UPDATE"#,
+        category_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Category id={} not found", category_id)))?;
+
+    if category.has_items {
+        return Err(AppError::Validation(format!(
+            "Categoria „{}” conține cerințe; mutați-le în coș înainte de a o șterge",
+            category.name
+        )));
+    }
+
+    sqlx::query!("DELETE FROM categories WHERE id = $1", category_id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+// ─────────────────────────── Reorder ───────────────────────────
+
+/// Set the display order of a project's categories.
+/// `category_ids` must list every category of the project exactly once.
+pub async fn reorder_categories(pool: &PgPool, project_id: i32, category_ids: &[i32]) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    guard::ensure_checklist_writable(&mut tx, project_id).await?;
+
+    let mut existing = sqlx::query_scalar!(
+        "SELECT id FROM categories WHERE project_id = $1",
+        project_id
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let mut requested = category_ids.to_vec();
+    existing.sort_unstable();
+    requested.sort_unstable();
+    if existing != requested {
+        return Err(AppError::Validation(format!(
+            "Ordinea trebuie să conțină exact categoriile proiectului {}",
+            project_id
+        )));
+    }
+
+    for (i, id) in category_ids.iter().enumerate() {
+        sqlx::query!(
+            "UPDATE categories SET sort_order = $1 WHERE id = $2",
+            i as i32, id
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+// ─────────────────────────── Import Checklist From Another Project ───────────────────────────
+
+/// Show what `import_checklist` would copy from `source_category_id` into `target_category_id`.
+pub async fn preview_checklist_import(
+    pool: &PgPool,
+    source_category_id: i32,
+    target_category_id: i32,
+) -> Result<ChecklistImportPreview> {
+    let source = sqlx::query!(
+        "SELECT c.name, p.title, p.year FROM categories c
+         JOIN projects p ON p.id = c.project_id
+         WHERE c.id = $1 AND p.deleted_at IS NULL",
+        source_category_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Category id={} not found", source_category_id)))?;
+
+    let target_item_count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "v!" FROM checklist_items WHERE category_id = $1 AND deleted_at IS NULL"#,
+        target_category_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    let (checklist, item_count, note_count) =
+        load_nested_checklist(pool, source_category_id).await?;
+
+    Ok(ChecklistImportPreview {
+        source_project: source.title,
+        source_year: source.year,
+        source_category: source.name,
+        item_count,
+        note_count,
+        target_item_count,
+        checklist,
+    })
+}
+
+/// Copy a category's full item tree from any project (any year) into a target category,
+/// either appending to or replacing its checklist. Returns the number of items copied.
+pub async fn import_checklist(
+    pool: &PgPool,
+    source_category_id: i32,
+    target_category_id: i32,
+    options: &ChecklistImportOptions,
+) -> Result<usize> {
+    if source_category_id == target_category_id {
+        return Err(AppError::Validation(
+            "Categoria sursă și categoria destinație sunt aceleași".into(),
+        ));
+    }
+
+    let mut tx = pool.begin().await?;
+    guard::ensure_category_writable(&mut tx, target_category_id).await?;
+
+    // Neither side may belong to a project in the trash
+    for id in [source_category_id, target_category_id] {
+        let exists = sqlx::query_scalar!(
+            r#"SELECT EXISTS(
+                   SELECT 1 FROM categories c JOIN projects p ON p.id = c.project_id
+                   WHERE c.id = $1 AND p.deleted_at IS NULL
+               ) AS "v!""#,
+            id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        if !exists {
+            return Err(AppError::NotFound(format!("Category id={} not found", id)));
+        }
+    }
+
+    let offset = match options.mode {
+        ImportMode::Replace => {
+            // Replaced items go to the trash (with their notes) and can be restored
+            sqlx::query!(
+                "UPDATE checklist_items SET deleted_at = now()
+                 WHERE category_id = $1 AND deleted_at IS NULL",
+                target_category_id
+            )
+            .execute(&mut *tx)
+            .await?;
+            0
+        }
+        ImportMode::Append => {
+            sqlx::query_scalar!(
+                "SELECT COALESCE(MAX(sort_order), -1) as \"v!\" FROM checklist_items
+                 WHERE category_id = $1 AND parent_id IS NULL AND deleted_at IS NULL",
+                target_category_id
+            )
+            .fetch_one(&mut *tx)
+            .await?
+                + 1
+        }
+    };
+
+    let cat_map = HashMap::from([(source_category_id, target_category_id)]);
+    let copied =
+        copy_checklist_items(&mut tx, &cat_map, options.keep_flags, options.keep_notes, offset)
+            .await?;
+
+    tx.commit().await?;
+    Ok(copied)
+}
+
+// ─────────────────────────── Helpers ───────────────────────────
+
+fn validate_name(name: &str) -> Result<&str> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::Validation("Numele categoriei nu poate fi gol".into()));
+    }
+    Ok(name)
+}
+
+fn duplicate_name(name: &str) -> AppError {
+    AppError::Validation(format!("Proiectul are deja o categorie numită '{}'", name))
+}
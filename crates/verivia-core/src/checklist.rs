@@ -1,116 +1,170 @@
 use sqlx::PgPool;
 
 use crate::errors::{AppError, Result};
-use crate::models::ChecklistItemNested;
+use crate::guard;
+use crate::models::{CategoryFull, ChecklistItemNested};
+use crate::tree::{load_nested_checklist, save_checklist_tree};
 
 // ─────────────────────────── Toggle Flags ───────────────────────────
 
+/// Set a flag on an item and all its live sub-tasks, at any depth.
 pub async fn toggle_flag(pool: &PgPool, item_id: i32, flag: &str, value: bool) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    guard::ensure_item_writable(&mut tx, item_id).await?;
+
     match flag {
         "proposed" => {
-            sqlx::query!("UPDATE checklist_items SET proposed = $1 WHERE id = $2", value, item_id)
-                .execute(pool).await?;
-            sqlx::query!("UPDATE checklist_items SET proposed = $1 WHERE parent_id = $2", value, item_id)
-                .execute(pool).await?;
+            sqlx::query!(
+                "WITH RECURSIVE subtree AS (
+                     SELECT id FROM checklist_items WHERE id = $2 AND deleted_at IS NULL
+                     UNION ALL
+                     SELECT c.id FROM checklist_items c JOIN subtree s ON c.parent_id = s.id
+                     WHERE c.deleted_at IS NULL
+                 )
+                 UPDATE checklist_items SET proposed = $1,
+                     proposed_at = CASE WHEN $1 THEN COALESCE(proposed_at, now()) END
+                 WHERE id IN (SELECT id FROM subtree)",
+                value, item_id
+            ).execute(&mut *tx).await?;
         }
         "verified" => {
-            sqlx::query!("UPDATE checklist_items SET verified = $1 WHERE id = $2", value, item_id)
-                .execute(pool).await?;
-            sqlx::query!("UPDATE checklist_items SET verified = $1 WHERE parent_id = $2", value, item_id)
-                .execute(pool).await?;
+            sqlx::query!(
+                "WITH RECURSIVE subtree AS (
+                     SELECT id FROM checklist_items WHERE id = $2 AND deleted_at IS NULL
+                     UNION ALL
+                     SELECT c.id FROM checklist_items c JOIN subtree s ON c.parent_id = s.id
+                     WHERE c.deleted_at IS NULL
+                 )
+                 UPDATE checklist_items SET verified = $1,
+                     verified_at = CASE WHEN $1 THEN COALESCE(verified_at, now()) END,
+                     needs_review = needs_review AND NOT $1,
+                     previous_name = CASE WHEN $1 THEN NULL ELSE previous_name END
+                 WHERE id IN (SELECT id FROM subtree)",
+                value, item_id
+            ).execute(&mut *tx).await?;
         }
         _ => return Err(AppError::Validation(format!("Unknown flag: {}", flag))),
     }
+    tx.commit().await?;
     Ok(())
 }
 
 // ─────────────────────────── Add / Edit / Delete ───────────────────────────
 
 pub async fn add_item(pool: &PgPool, category_id: i32, parent_id: Option<i32>, name: &str) -> Result<i32> {
+    let mut tx = pool.begin().await?;
+    guard::ensure_category_writable(&mut tx, category_id).await?;
+
     let max_order: i32 = if let Some(pid) = parent_id {
         sqlx::query_scalar!(
-            "SELECT COALESCE(MAX(sort_order), -1) as \"v!\" FROM checklist_items WHERE parent_id = $1", pid
-        ).fetch_one(pool).await?
+            "SELECT COALESCE(MAX(sort_order), -1) as \"v!\" FROM checklist_items WHERE parent_id = $1 AND deleted_at IS NULL", pid
+        ).fetch_one(&mut *tx).await?
     } else {
         sqlx::query_scalar!(
-            "SELECT COALESCE(MAX(sort_order), -1) as \"v!\" FROM checklist_items WHERE category_id = $1 AND parent_id IS NULL", category_id
-        ).fetch_one(pool).await?
+            "SELECT COALESCE(MAX(sort_order), -1) as \"v!\" FROM checklist_items WHERE category_id = $1 AND parent_id IS NULL AND deleted_at IS NULL", category_id
+        ).fetch_one(&mut *tx).await?
     };
 
     let id = sqlx::query_scalar!(
         "INSERT INTO checklist_items (category_id, parent_id, name, sort_order) VALUES ($1, $2, $3, $4) RETURNING id",
         category_id, parent_id, name, max_order + 1
-    ).fetch_one(pool).await?;
+    ).fetch_one(&mut *tx).await?;
 
+    tx.commit().await?;
     Ok(id)
 }
 
 pub async fn edit_item(pool: &PgPool, item_id: i32, new_name: &str) -> Result<()> {
-    let rows = sqlx::query!("UPDATE checklist_items SET name = $1 WHERE id = $2", new_name, item_id)
-        .execute(pool).await?.rows_affected();
+    let mut tx = pool.begin().await?;
+    guard::ensure_item_writable(&mut tx, item_id).await?;
+
+    let rows = sqlx::query!(
+        "UPDATE checklist_items SET name = $1 WHERE id = $2 AND deleted_at IS NULL", new_name, item_id
+    )
+        .execute(&mut *tx).await?.rows_affected();
     if rows == 0 {
         return Err(AppError::NotFound(format!("Checklist item id={} not found", item_id)));
     }
+    tx.commit().await?;
     Ok(())
 }
 
+/// Set our offer's response to a requirement and the document that supports it.
+/// Blank values clear the field.
+pub async fn set_item_response(
+    pool: &PgPool,
+    item_id: i32,
+    response: Option<&str>,
+    document_reference: Option<&str>,
+) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    guard::ensure_item_writable(&mut tx, item_id).await?;
+
+    let response = response.map(str::trim).filter(|s| !s.is_empty());
+    let document_reference = document_reference.map(str::trim).filter(|s| !s.is_empty());
+    let rows = sqlx::query!(
+        "UPDATE checklist_items SET response = $1, document_reference = $2 WHERE id = $3 AND deleted_at IS NULL",
+        response, document_reference, item_id
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    if rows == 0 {
+        return Err(AppError::NotFound(format!("Checklist item id={} not found", item_id)));
+    }
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Move an item and its sub-tasks to the trash (see `trash::restore_item`).
 pub async fn delete_item(pool: &PgPool, item_id: i32) -> Result<()> {
-    sqlx::query!("DELETE FROM checklist_items WHERE id = $1", item_id)
-        .execute(pool).await?;
+    let mut tx = pool.begin().await?;
+    guard::ensure_item_writable(&mut tx, item_id).await?;
+
+    sqlx::query!(
+        "WITH RECURSIVE subtree AS (
+             SELECT id FROM checklist_items WHERE id = $1 AND deleted_at IS NULL
+             UNION ALL
+             SELECT c.id FROM checklist_items c JOIN subtree s ON c.parent_id = s.id
+             WHERE c.deleted_at IS NULL
+         )
+         UPDATE checklist_items SET deleted_at = now() WHERE id IN (SELECT id FROM subtree)",
+        item_id
+    ).execute(&mut *tx).await?;
+    tx.commit().await?;
     Ok(())
 }
 
 // ─────────────────────────── Bulk Save (transactional, iterative) ───────────────────────────
-// Two-level iteration (parent + subtasks) — matches the UI structure exactly.
+// Saves the given tree over the live items, at any nesting depth; what it no longer
+// contains goes to the trash.
 //
 // KNOWN LIMITATION: Last-writer-wins. If user A and user B both open the same
 // category, and B saves while A is still editing, A's save will overwrite B's
 // changes (including notes). This is acceptable for the current 2-3 user scale.
 // TODO: Move to granular operations (per-item toggle/note API calls) to eliminate this.
 
+/// `excel_hash` is given when `items` were just read from the category's spreadsheet
+/// (the hash of the file as it was parsed): it becomes the import `linked_excel` compares with.
 pub async fn save_category_checklist(
     pool: &PgPool,
     category_id: i32,
     items: &[ChecklistItemNested],
+    excel_hash: Option<&str>,
 ) -> Result<()> {
     let mut tx = pool.begin().await?;
+    guard::ensure_category_writable(&mut tx, category_id).await?;
 
-    // Delete existing (CASCADE removes notes too)
-    sqlx::query!("DELETE FROM checklist_items WHERE category_id = $1", category_id)
-        .execute(&mut *tx).await?;
-
-    for (i, item) in items.iter().enumerate() {
-        // Insert parent item
-        let parent_id = sqlx::query_scalar!(
-            "INSERT INTO checklist_items (category_id, parent_id, name, proposed, verified, sort_order, proposed_by, verified_by)
-             VALUES ($1, NULL, $2, $3, $4, $5, $6, $7) RETURNING id",
-            category_id, item.name, item.proposed, item.verified, i as i32, item.proposed_by, item.verified_by
-        ).fetch_one(&mut *tx).await?;
+    save_checklist_tree(&mut tx, category_id, items).await?;
 
-        // Insert parent notes
-        for note in &item.notes {
-            sqlx::query!(
-                r#"INSERT INTO notes (item_id, "user", date, text) VALUES ($1, $2, $3, $4)"#,
-                parent_id, note.user, note.date, note.text
-            ).execute(&mut *tx).await?;
-        }
-
-        // Insert subtasks
-        for (j, sub) in item.sub_tasks.iter().enumerate() {
-            let sub_id = sqlx::query_scalar!(
-                "INSERT INTO checklist_items (category_id, parent_id, name, proposed, verified, sort_order, proposed_by, verified_by)
-                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8) RETURNING id",
-                category_id, parent_id, sub.name, sub.proposed, sub.verified, j as i32, sub.proposed_by, sub.verified_by
-            ).fetch_one(&mut *tx).await?;
-
-            // Insert subtask notes
-            for note in &sub.notes {
-                sqlx::query!(
-                    r#"INSERT INTO notes (item_id, "user", date, text) VALUES ($1, $2, $3, $4)"#,
-                    sub_id, note.user, note.date, note.text
-                ).execute(&mut *tx).await?;
-            }
-        }
+    if let Some(hash) = excel_hash {
+        sqlx::query!(
+            "UPDATE categories SET excel_hash = $1 WHERE id = $2 AND excel_path <> ''",
+            hash, category_id
+        )
+        .execute(&mut *tx)
+        .await?;
     }
 
     tx.commit().await?;
@@ -119,11 +173,25 @@ pub async fn save_category_checklist(
 
 // ─────────────────────────── Save Excel Path ───────────────────────────
 
-pub async fn save_excel_path(pool: &PgPool, project_id: i32, path: &str) -> Result<()> {
-    sqlx::query!(
-        "UPDATE categories SET excel_path = $1 WHERE project_id = $2 AND LOWER(name) = 'tehnic'",
-        path, project_id
-    ).execute(pool).await?;
+/// Attach an Excel file to a category. Any category may hold one, not only Tehnic.
+/// Parsing a file is not importing it: a different file clears the recorded import
+/// (hash, sheet, profile) until the checklist read from it is saved (see `linked_excel`).
+pub async fn save_excel_path(pool: &PgPool, category_id: i32, path: &str) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    guard::ensure_category_writable(&mut tx, category_id).await?;
+
+    let rows = sqlx::query!(
+        "UPDATE categories SET excel_path = $1,
+             excel_hash = CASE WHEN excel_path IS DISTINCT FROM $1 THEN NULL ELSE excel_hash END,
+             excel_sheet = CASE WHEN excel_path IS DISTINCT FROM $1 THEN NULL ELSE excel_sheet END,
+             excel_profile_id = CASE WHEN excel_path IS DISTINCT FROM $1 THEN NULL ELSE excel_profile_id END
+         WHERE id = $2",
+        path, category_id
+    ).execute(&mut *tx).await?.rows_affected();
+    if rows == 0 {
+        return Err(AppError::NotFound(format!("Category id={} not found", category_id)));
+    }
+    tx.commit().await?;
     Ok(())
 }
 
@@ -140,3 +208,27 @@ pub async fn get_category_id(pool: &PgPool, project_id: i32, category_name: &str
         "Category '{}' not found for project {}", category_name, project_id
     )))
 }
+
+// ─────────────────────────── Load One Category ───────────────────────────
+
+/// The nested checklist (with notes) of a single category, loaded on demand
+/// when the user opens it from the lightweight project list.
+pub async fn load_category(pool: &PgPool, project_id: i32, category_name: &str) -> Result<CategoryFull> {
+    let category = sqlx::query!(
+        "SELECT id, name, excel_path FROM categories WHERE project_id = $1 AND LOWER(name) = $2",
+        project_id, category_name.to_lowercase()
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!(
+        "Category '{}' not found for project {}", category_name, project_id
+    )))?;
+
+    let (checklist, _, _) = load_nested_checklist(pool, category.id).await?;
+
+    Ok(CategoryFull {
+        name: category.name,
+        excel_path: category.excel_path,
+        checklist,
+    })
+}
@@ -0,0 +1,110 @@
+use sqlx::PgPool;
+
+use crate::errors::{AppError, Result};
+use crate::guard;
+use crate::models::{DeadlineAlert, ProjectDeadlines};
+
+// ─────────────────────────── Set Deadlines ───────────────────────────
+
+pub async fn set_project_deadlines(
+    pool: &PgPool,
+    project_id: i32,
+    deadlines: &ProjectDeadlines,
+) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    guard::ensure_project_writable(&mut tx, project_id).await?;
+
+    // Clarification requests must be sent before the bid is submitted
+    if let (Some(clarification), Some(submission)) =
+        (deadlines.clarification_deadline, deadlines.submission_deadline)
+    {
+        if clarification > submission {
+            return Err(AppError::Validation(
+                "Termenul de clarificări nu poate fi după termenul de depunere".into(),
+            ));
+        }
+    }
+
+    let rows = sqlx::query!(
+        "UPDATE projects
+         SET submission_deadline = $1, clarification_deadline = $2, opening_date = $3
+         WHERE id = $4 AND deleted_at IS NULL",
+        deadlines.submission_deadline,
+        deadlines.clarification_deadline,
+        deadlines.opening_date,
+        project_id
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    if rows == 0 {
+        return Err(AppError::NotFound(format!("Project id={} not found", project_id)));
+    }
+    tx.commit().await?;
+    Ok(())
+}
+
+// ─────────────────────────── Upcoming / Overdue ───────────────────────────
+// A category counts as incomplete while any of its items is not both proposed and verified.
+// Only projects still in preparation are reported: once submitted, awarded or cancelled
+// their deadline no longer matters.
+
+/// Projects whose submission deadline falls within the next `days` days
+/// and that still have unverified items. Soonest deadline first.
+pub async fn projects_due_within(pool: &PgPool, days: i32) -> Result<Vec<DeadlineAlert>> {
+    if days < 0 {
+        return Err(AppError::Validation("Numărul de zile nu poate fi negativ".into()));
+    }
+
+    let alerts = sqlx::query_as!(
+        DeadlineAlert,
+        r#"SELECT p.id AS project_id,
+                  p.title,
+                  p.year,
+                  p.submission_deadline AS "submission_deadline!",
+                  ARRAY_AGG(DISTINCT c.name ORDER BY c.name) AS "incomplete_categories!",
+                  COUNT(i.id) AS "unverified_items!"
+           FROM projects p
+           JOIN categories c ON c.project_id = p.id
+           JOIN checklist_items i ON i.category_id = c.id
+           WHERE p.deleted_at IS NULL AND i.deleted_at IS NULL
+             AND p.status = 'in_preparation'
+             AND p.submission_deadline >= now()
+             AND p.submission_deadline < now() + make_interval(days => $1)
+             AND NOT (i.proposed AND i.verified)
+           GROUP BY p.id
+           ORDER BY p.submission_deadline"#,
+        days
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(alerts)
+}
+
+/// Projects whose submission deadline has already passed with items still unverified.
+pub async fn overdue_projects(pool: &PgPool) -> Result<Vec<DeadlineAlert>> {
+    let alerts = sqlx::query_as!(
+        DeadlineAlert,
+        r#"SELECT p.id AS project_id,
+                  p.title,
+                  p.year,
+                  p.submission_deadline AS "submission_deadline!",
+                  ARRAY_AGG(DISTINCT c.name ORDER BY c.name) AS "incomplete_categories!",
+                  COUNT(i.id) AS "unverified_items!"
+           FROM projects p
+           JOIN categories c ON c.project_id = p.id
+           JOIN checklist_items i ON i.category_id = c.id
+           WHERE p.deleted_at IS NULL AND i.deleted_at IS NULL
+             AND p.status = 'in_preparation'
+             AND p.submission_deadline < now()
+             AND NOT (i.proposed AND i.verified)
+           GROUP BY p.id
+           ORDER BY p.submission_deadline DESC"#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(alerts)
+}
@@ -22,6 +22,9 @@ pub enum AppError {
 
     #[error("Excel error: {0}")]
     Excel(String),
+
+    #[error("Archive error: {0}")]
+    Archive(String),
 }
 
 // Convenience alias used throughout the crate
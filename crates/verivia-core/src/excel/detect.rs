@@ -0,0 +1,214 @@
+//! Header and requirement-column detection for spreadsheets with an unknown layout.
+
+use calamine::{DataType, Range};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use super::styles::CellStyles;
+use super::{cell_text, collect_rows, open_source, to_position};
+use crate::errors::{AppError, Result};
+use crate::models::{ImportReport, LayoutDetection};
+
+/// Below this the detection is only a suggestion; `parse_technical_excel` falls back
+/// to the built-in layout.
+pub const MIN_CONFIDENCE: f64 = 0.5;
+
+/// Rows searched for a header from the top of the sheet.
+const HEADER_SCAN_ROWS: u32 = 30;
+/// Rows below the header sampled to judge column content.
+const CONTENT_SCAN_ROWS: u32 = 200;
+const SAMPLE_SIZE: usize = 5;
+
+/// Header words (diacritics folded) of a requirement column, matched as word prefixes.
+const REQUIREMENT_KEYWORDS: &[&str] = &[
+    "cerint", "specificat", "denumire", "descriere", "caracteristic", "requirement", "description",
+];
+/// Header words of a numbering column, matched as whole words.
+const REFERENCE_KEYWORDS: &[&str] = &["nr", "crt", "cod", "pozitie", "poz", "ref", "no", "item"];
+/// Header words of our response column ("Propunere tehnică", "Răspuns ofertant"), as prefixes.
+const RESPONSE_KEYWORDS: &[&str] = &["raspuns", "propuner", "conformitat", "ofert", "response", "complian"];
+/// Header words of the column locating the supporting document, as prefixes.
+const DOCUMENT_KEYWORDS: &[&str] = &["document", "pagin", "pag", "anex", "sectiun", "page", "annex"];
+
+lazy_static! {
+    static ref RE_NUMBERING: Regex =
+        Regex::new(r"(?i)^(\d+(\.\d+)*\.?|[a-z][\.\)]|[ivx]+[\.\)]?)$").unwrap();
+}
+
+// ─────────────────────────── Detect ───────────────────────────
+
+/// Find the header row and requirement column of `sheet_name`, or of the most
+/// promising sheet when `None`. Nothing is imported; the report (with a sample)
+/// lets the user confirm, then `LayoutDetection::profile` imports it.
+pub fn detect_layout(file_path: &str, sheet_name: Option<&str>) -> Result<LayoutDetection> {
+    let mut source = open_source(file_path)?;
+    let sheet_names = source.sheet_names();
+
+    let mut best: Option<LayoutDetection> = None;
+    for (i, name) in sheet_names.iter().enumerate() {
+        if sheet_name.is_some_and(|wanted| !name.trim().eq_ignore_ascii_case(wanted.trim())) {
+            continue;
+        }
+        let range = source.sheet(name)?;
+        let Some(detection) = detect_in(&range, name, i) else { continue };
+        if best.as_ref().is_none_or(|b| detection.confidence > b.confidence) {
+            best = Some(detection);
+        }
+    }
+
+    best.ok_or_else(|| match sheet_name {
+        Some(wanted) if !sheet_names.iter().any(|n| n.trim().eq_ignore_ascii_case(wanted.trim())) => {
+            AppError::Excel(format!("Sheet '{}' not found", wanted))
+        }
+        Some(wanted) => AppError::Excel(format!("No requirements found in sheet '{}'", wanted)),
+        None => AppError::Excel("No requirements found in the spreadsheet".into()),
+    })
+}
+
+/// `None` if the sheet has no text at all.
+fn detect_in(range: &Range<DataType>, sheet_name: &str, sheet_idx: usize) -> Option<LayoutDetection> {
+    let (start, end) = (range.start()?, range.end()?);
+    let header = find_header(range);
+    let first_row = header.map_or(start.0, |h| h + 1);
+    let last_row = end.0.min(first_row + CONTENT_SCAN_ROWS);
+
+    // ─── Content of each column below the header ───
+    let rows_with_data = (first_row..=last_row)
+        .filter(|&r| (start.1..=end.1).any(|c| !cell_text(range, r, c).is_empty()))
+        .count()
+        .max(1) as f64;
+
+    let header_title = |c: u32| header.map(|h| cell_text(range, h, c)).unwrap_or_default();
+
+    let mut name_col = None;
+    let mut best_score = 0.0;
+    let mut best_content = 0.0;
+    for c in start.1..=end.1 {
+        let texts: Vec<String> = (first_row..=last_row)
+            .map(|r| cell_text(range, r, c))
+            .filter(|t| !t.is_empty() && t.parse::<f64>().is_err() && !RE_NUMBERING.is_match(t))
+            .collect();
+        if texts.is_empty() {
+            continue;
+        }
+        let avg_len = texts.iter().map(|t| t.chars().count()).sum::<usize>() as f64 / texts.len() as f64;
+        let content = (texts.len() as f64 / rows_with_data).min(1.0) * (avg_len / 20.0).min(1.0);
+        let keyword_bonus = if is_requirement_title(&header_title(c)) { 0.5 } else { 0.0 };
+
+        if content + keyword_bonus > best_score {
+            best_score = content + keyword_bonus;
+            best_content = content;
+            name_col = Some(c);
+        }
+    }
+    let name_col = name_col?;
+    let name_title = Some(header_title(name_col)).filter(|t| !t.is_empty());
+
+    // ─── Numbering column left of the names: mostly outline numbers, else titled like one ───
+    let name_rows: Vec<u32> = (first_row..=last_row)
+        .filter(|&r| !cell_text(range, r, name_col).is_empty())
+        .collect();
+    let reference_col = (start.1..name_col)
+        .rev()
+        .find(|&c| {
+            let numbered = name_rows
+                .iter()
+                .filter(|&&r| RE_NUMBERING.is_match(&cell_text(range, r, c)))
+                .count();
+            !name_rows.is_empty() && numbered * 2 >= name_rows.len()
+        })
+        .or_else(|| (start.1..name_col).rev().find(|&c| is_reference_title(&header_title(c))));
+
+    // ─── Response / document columns right of the names, by their header title only ───
+    let titled = |is_title: fn(&str) -> bool| (name_col + 1..=end.1).find(|&c| is_title(&header_title(c)));
+    let document_col = titled(is_document_title);
+    let response_col = titled(is_response_title).filter(|&c| Some(c) != document_col);
+
+    let header_score = match (header, &name_title) {
+        (Some(_), Some(title)) if is_requirement_title(title) => 1.0,
+        (Some(_), _) => 0.5,
+        (None, _) => 0.0,
+    };
+    let confidence = ((header_score + best_content) / 2.0 * 100.0).round() / 100.0;
+
+    let detection = LayoutDetection {
+        sheet_name: sheet_name.to_string(),
+        sheet_index: sheet_idx as i32 + 1,
+        header_row: header.map(to_position),
+        name_column: to_position(name_col),
+        name_title,
+        reference_column: reference_col.map(to_position),
+        response_column: response_col.map(to_position),
+        document_reference_column: document_col.map(to_position),
+        data_rows: 0,
+        sample: Vec::new(),
+        confidence,
+    };
+
+    let rows = collect_rows(range, &detection.profile(), &CellStyles::default(), &mut ImportReport::default());
+    Some(LayoutDetection {
+        data_rows: rows.len(),
+        sample: rows.iter().take(SAMPLE_SIZE).map(|r| r.display()).collect(),
+        ..detection
+    })
+}
+
+/// 0-based index of the row that best looks like a header (requirement keyword
+/// required, numbering keywords count too) among the first rows of the sheet.
+pub(super) fn find_header(range: &Range<DataType>) -> Option<u32> {
+    let (start, end) = (range.start()?, range.end()?);
+    let mut best: Option<(u32, usize)> = None;
+
+    for r in start.0..=end.0.min(start.0 + HEADER_SCAN_ROWS) {
+        let titles: Vec<String> = (start.1..=end.1).map(|c| cell_text(range, r, c)).collect();
+        let requirement = titles.iter().filter(|t| is_requirement_title(t)).count();
+        if requirement == 0 {
+            continue;
+        }
+        let score = requirement * 2 + titles.iter().filter(|t| is_reference_title(t)).count();
+        if best.is_none_or(|(_, s)| score > s) {
+            best = Some((r, score));
+        }
+    }
+
+    best.map(|(r, _)| r)
+}
+
+// ─────────────────────────── Keywords ───────────────────────────
+
+fn is_requirement_title(title: &str) -> bool {
+    // Long cells are content, not column titles
+    title.chars().count() <= 60
+        && words(title).any(|w| REQUIREMENT_KEYWORDS.iter().any(|k| w.starts_with(k)))
+}
+
+fn is_reference_title(title: &str) -> bool {
+    title.chars().count() <= 20 && words(title).any(|w| REFERENCE_KEYWORDS.contains(&w.as_str()))
+}
+
+fn is_response_title(title: &str) -> bool {
+    title.chars().count() <= 60
+        && !is_requirement_title(title)
+        && words(title).any(|w| RESPONSE_KEYWORDS.iter().any(|k| w.starts_with(k)))
+}
+
+fn is_document_title(title: &str) -> bool {
+    title.chars().count() <= 60 && words(title).any(|w| DOCUMENT_KEYWORDS.iter().any(|k| w.starts_with(k)))
+}
+
+/// Lowercase words with Romanian diacritics folded ("Cerință" → "cerinta").
+pub(crate) fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase().chars().map(fold_diacritic).collect())
+}
+
+fn fold_diacritic(c: char) -> char {
+    match c {
+        'ă' | 'â' => 'a',
+        'î' => 'i',
+        'ș' | 'ş' => 's',
+        'ț' | 'ţ' => 't',
+        other => other,
+    }
+}
@@ -0,0 +1,311 @@
+use calamine::{DataType, Range};
+use chrono::{Duration, NaiveDate};
+
+use crate::errors::{AppError, Result};
+use crate::models::{ChecklistItemNested, ImportProfile, ImportReport, IssueKind, ParsedChecklist, SheetInfo};
+
+mod detect;
+mod outline;
+mod report;
+mod source;
+mod styles;
+
+pub use detect::{detect_layout, MIN_CONFIDENCE};
+pub(crate) use detect::words;
+
+use outline::OutlineRow;
+use source::{open_source, ImportSource};
+use styles::CellStyles;
+
+/// Parse an Excel file and extract checklist items for the Tehnic category.
+/// The header and requirement column are detected (see `detect_layout`); when the
+/// detection is not confident enough the built-in layout is used (`ImportProfile::default`).
+pub fn parse_technical_excel(file_path: &str) -> Result<Vec<ChecklistItemNested>> {
+    Ok(parse_technical_excel_report(file_path)?.checklist)
+}
+
+/// `parse_technical_excel` with the review of what was skipped or converted.
+pub fn parse_technical_excel_report(file_path: &str) -> Result<ParsedChecklist> {
+    let profile = match detect_layout(file_path, None) {
+        Ok(detection) if detection.confidence >= MIN_CONFIDENCE => detection.profile(),
+        _ => ImportProfile::default(),
+    };
+    parse_excel_report(file_path, &profile)
+}
+
+/// Parse the requirements of a spreadsheet, CSV file or Word table (see `source`) laid
+/// out as described by `profile`. The hierarchy follows the numbering, indentation and
+/// bold headings (see `outline`); the numbering is kept as the item reference and the
+/// description follows the name.
+pub fn parse_excel(file_path: &str, profile: &ImportProfile) -> Result<Vec<ChecklistItemNested>> {
+    Ok(parse_excel_report(file_path, profile)?.checklist)
+}
+
+/// `parse_excel` with the review of the import (see `ImportReport`): rows skipped and
+/// why, numbers and dates taken as text, merged cells, repeated and overly long requirements.
+pub fn parse_excel_report(file_path: &str, profile: &ImportProfile) -> Result<ParsedChecklist> {
+    let mut source = open_source(file_path)?;
+    parse_sheet(source.as_mut(), profile)
+}
+
+/// Parse several sheets of one document (opened once), one profile per sheet.
+pub fn parse_sheets(file_path: &str, profiles: &[ImportProfile]) -> Result<Vec<ParsedChecklist>> {
+    let mut source = open_source(file_path)?;
+    profiles
+        .iter()
+        .map(|profile| parse_sheet(source.as_mut(), profile))
+        .collect()
+}
+
+/// Every sheet (or Word table) of the document with its number of non-empty rows.
+pub fn list_sheets(file_path: &str) -> Result<Vec<SheetInfo>> {
+    let mut source = open_source(file_path)?;
+
+    source
+        .sheet_names()
+        .into_iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let range = source.sheet(&name)?;
+            let rows = range
+                .rows()
+                .filter(|row| row.iter().any(|c| !matches!(c, DataType::Empty)))
+                .count();
+            Ok(SheetInfo { name, index: to_position(i as u32), rows })
+        })
+        .collect()
+}
+
+// ─────────────────────────── Reading ───────────────────────────
+
+fn parse_sheet(source: &mut dyn ImportSource, profile: &ImportProfile) -> Result<ParsedChecklist> {
+    let (sheet, range) = read_sheet(source, profile)?;
+    let styles = source.styles(&sheet);
+    let mut report = ImportReport { sheet_name: sheet, ..Default::default() };
+    let rows = collect_rows(&range, profile, &styles, &mut report);
+    report::review_rows(&mut report, &rows, to_index(profile.name_column));
+    Ok(ParsedChecklist { checklist: outline::build_tree(rows), report })
+}
+
+/// The profile's sheet (name and cells): by name, else by index.
+fn read_sheet(source: &mut dyn ImportSource, profile: &ImportProfile) -> Result<(String, Range<DataType>)> {
+    let sheet_names = source.sheet_names();
+    let sheet = match profile.sheet_name.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+        Some(wanted) => sheet_names
+            .iter()
+            .find(|s| s.trim().eq_ignore_ascii_case(wanted))
+            .ok_or_else(|| AppError::Excel(format!("Sheet '{}' not found", wanted)))?,
+        None => sheet_names
+            .get(to_index(profile.sheet_index) as usize)
+            .ok_or_else(|| AppError::Excel(format!("Sheet #{} not found", profile.sheet_index)))?,
+    }
+    .clone();
+
+    let range = source.sheet(&sheet)?;
+    Ok((sheet, range))
+}
+
+/// Requirement rows below the header, up to the end marker, with the reference, our
+/// response and the formatting of the requirement cell. A description is appended to the text.
+/// Rows that could not be taken as is are recorded in `report`.
+fn collect_rows(
+    range: &Range<DataType>,
+    profile: &ImportProfile,
+    styles: &CellStyles,
+    report: &mut ImportReport,
+) -> Vec<OutlineRow> {
+    let (Some(start), Some(end)) = (range.start(), range.end()) else {
+        return Vec::new();
+    };
+
+    let name_col = to_index(profile.name_column);
+    let first_row = match profile.header_row {
+        Some(row) => to_index(row) + 1,
+        None => detect::find_header(range).map_or(start.0, |h| h + 1),
+    };
+    let end_marker = profile
+        .end_marker
+        .as_deref()
+        .map(|m| m.trim().to_lowercase())
+        .filter(|m| !m.is_empty());
+
+    let mut rows = Vec::new();
+    for row in first_row..=end.0 {
+        if let Some(marker) = &end_marker {
+            let is_end = (start.1..=end.1)
+                .any(|c| cell_text(range, row, c).to_lowercase().starts_with(marker.as_str()));
+            if is_end {
+                report::end_marker(report, range, row);
+                break;
+            }
+        }
+
+        report.rows_read += 1;
+        if (start.1..=end.1).all(|c| cell_text(range, row, c).is_empty()) {
+            report.empty_rows += 1;
+            continue;
+        }
+        let Some(name) = requirement_cell(range, styles, row, name_col, report) else {
+            continue;
+        };
+        report.rows_imported += 1;
+
+        // Numbers and dates in the reference column are expected ("1.2"), not reported
+        for column in [profile.description_column, profile.response_column, profile.document_reference_column]
+            .into_iter()
+            .flatten()
+        {
+            report::converted(report, range, row, to_index(column));
+        }
+        let optional = |column: Option<i32>| {
+            column.map(|c| cell_text(range, row, to_index(c))).filter(|t| !t.is_empty())
+        };
+        let text = match profile.description_column.map(|c| cell_text(range, row, to_index(c))) {
+            Some(d) if !d.is_empty() => format!("{}: {}", name, d),
+            _ => name,
+        };
+        // A title merged across from the reference column is the requirement, not its
+        // reference; its formatting is that of the merged cell
+        let cell = match styles.merged_origin(row, name_col) {
+            Some(origin) if origin.0 == row => origin,
+            _ => (row, name_col),
+        };
+        let reference = optional(profile.reference_column)
+            .filter(|_| profile.reference_column.map(to_index) != Some(cell.1));
+        // Two leading spaces count as one indentation level, like a cell indent
+        let indent = styles.indent(cell.0, cell.1) + leading_spaces(range, cell.0, cell.1) / 2;
+
+        rows.push(OutlineRow {
+            row,
+            reference,
+            text,
+            indent,
+            bold: styles.bold(cell.0, cell.1),
+            response: optional(profile.response_column),
+            document_reference: optional(profile.document_reference_column),
+        });
+    }
+
+    rows
+}
+
+/// Text of the requirement cell of a row with content; `None` (and the reason in
+/// `report`) when the row yields no requirement. A cell covered by a merged region takes
+/// the region's text when the region spans the row only; the rows below the first one of
+/// a vertically merged region belong to that first row.
+fn requirement_cell(
+    range: &Range<DataType>,
+    styles: &CellStyles,
+    row: u32,
+    col: u32,
+    report: &mut ImportReport,
+) -> Option<String> {
+    if let Some(DataType::Error(e)) = range.get_value((row, col)) {
+        let message = format!("Celula cerinței conține o eroare ({}); rândul nu a fost importat", e);
+        report::add(report, IssueKind::SkippedRow, row, Some(col), message);
+        return None;
+    }
+
+    let text = cell_text(range, row, col);
+    if !text.is_empty() {
+        report::converted(report, range, row, col);
+        return Some(text);
+    }
+
+    match styles.merged_origin(row, col) {
+        Some((origin_row, origin_col)) if origin_row == row => {
+            let text = cell_text(range, origin_row, origin_col);
+            let origin = report::cell_ref(origin_row, origin_col);
+            if text.is_empty() {
+                let message = format!("Celula cerinței e îmbinată cu {}, care e goală; rândul nu a fost importat", origin);
+                report::add(report, IssueKind::SkippedRow, row, Some(col), message);
+                return None;
+            }
+            let message = format!("Celula cerinței e îmbinată; textul a fost preluat din {}", origin);
+            report::add(report, IssueKind::MergedCell, row, Some(col), message);
+            Some(text)
+        }
+        Some((origin_row, _)) => {
+            let message = format!(
+                "Celula cerinței e îmbinată cu cea de pe rândul {}; celelalte valori ale rândului nu au fost importate",
+                to_position(origin_row)
+            );
+            report::add(report, IssueKind::MergedCell, row, Some(col), message);
+            None
+        }
+        None => {
+            let content = (0..=range.end().map_or(0, |e| e.1))
+                .map(|c| cell_text(range, row, c))
+                .find(|t| !t.is_empty())
+                .unwrap_or_default();
+            let message = format!("Coloana cerinței e goală; rândul conține „{}”", report::excerpt(&content));
+            report::add(report, IssueKind::SkippedRow, row, Some(col), message);
+            None
+        }
+    }
+}
+
+// ─────────────────────────── Helpers ───────────────────────────
+
+/// 1-based spreadsheet position → 0-based calamine index.
+fn to_index(position: i32) -> u32 {
+    position.max(1) as u32 - 1
+}
+
+/// 0-based calamine index → 1-based spreadsheet position.
+fn to_position(index: u32) -> i32 {
+    index as i32 + 1
+}
+
+/// Trimmed text of a cell (numbers as displayed, e.g. 1.1; dates as 01.03.2026);
+/// empty if missing.
+fn cell_text(range: &Range<DataType>, row: u32, col: u32) -> String {
+    match range.get_value((row, col)) {
+        Some(DataType::Empty) | None => String::new(),
+        Some(DataType::DateTime(serial)) => excel_date(*serial),
+        Some(value) => value.to_string().trim().to_string(),
+    }
+}
+
+/// An Excel date serial (days since 1899-12-30, time as the fraction) as displayed.
+/// A serial outside the calendar (corrupt cell) keeps its number.
+fn excel_date(serial: f64) -> String {
+    let epoch = NaiveDate::from_ymd_opt(1899, 12, 30).unwrap_or_default().and_time(Default::default());
+    let date = Duration::try_milliseconds((serial * 86_400_000.0).round() as i64)
+        .and_then(|offset| epoch.checked_add_signed(offset));
+    let Some(date) = date else { return serial.to_string() };
+    let format = match (serial.trunc() == 0.0, serial.fract() == 0.0) {
+        (true, false) => "%H:%M",
+        (false, true) => "%d.%m.%Y",
+        _ => "%d.%m.%Y %H:%M",
+    };
+    date.format(format).to_string()
+}
+
+fn leading_spaces(range: &Range<DataType>, row: u32, col: u32) -> u32 {
+    match range.get_value((row, col)) {
+        Some(DataType::String(s)) => s.chars().take_while(|c| *c == ' ').count() as u32,
+        _ => 0,
+    }
+}
+
+fn new_nested_item(name: &str) -> ChecklistItemNested {
+    ChecklistItemNested {
+        id: 0,
+        name: name.to_string(),
+        proposed: false,
+        verified: false,
+        status: "incomplete".to_string(),
+        notes: Vec::new(),
+        sub_tasks: Vec::new(),
+        proposed_by: None,
+        verified_by: None,
+        proposed_at: None,
+        verified_at: None,
+        reference: None,
+        needs_review: false,
+        previous_name: None,
+        response: None,
+        document_reference: None,
+    }
+}
@@ -0,0 +1,432 @@
+//! Requirement hierarchy from the numbering, indentation and formatting of the rows.
+//!
+//! "1." / "1.1" / "1.1.1" nest by their number of levels; lists ("a)", "i.", "1)",
+//! bullets) nest under the row above them and continue while the same kind of marker
+//! repeats; indented rows nest under the last less indented one; bold unnumbered rows
+//! are section headings. The numbering is kept as the item reference.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use super::new_nested_item;
+use crate::models::ChecklistItemNested;
+
+/// One requirement row as read from the sheet.
+#[derive(Debug, Clone)]
+pub struct OutlineRow {
+    /// 0-based sheet row, for the import report
+    pub row: u32,
+    /// Value of the reference column, if the layout has one.
+    pub reference: Option<String>,
+    /// Requirement text, possibly starting with its numbering.
+    pub text: String,
+    /// Indentation level (cell indent, or leading spaces).
+    pub indent: u32,
+    pub bold: bool,
+    /// Our response and its document reference, from the profile's columns
+    pub response: Option<String>,
+    pub document_reference: Option<String>,
+}
+
+impl OutlineRow {
+    /// The row as shown in previews: reference first, unless the text starts with it.
+    pub fn display(&self) -> String {
+        match self.reference.as_deref().filter(|r| !r.is_empty() && !self.text.starts_with(r)) {
+            Some(r) => format!("{} {}", r, self.text),
+            None => self.text.clone(),
+        }
+    }
+}
+
+lazy_static! {
+    // Outline number: "1.2.3" with optional trailing dot, or a single number with a dot
+    static ref RE_DECIMAL: Regex = Regex::new(r"^(\d{1,3}(?:\.\d{1,3})+\.?|\d{1,3}\.)(?:\s+|$)").unwrap();
+    static ref RE_BARE_NUMBER: Regex = Regex::new(r"^\d+(\.\d+)*\.?$").unwrap();
+    static ref RE_NUMBER_PAREN: Regex = Regex::new(r"^(\(?\d{1,3}\))(?:\s+|$)").unwrap();
+    static ref RE_LETTER: Regex = Regex::new(r"^(\(?[a-zA-Z][\.\)])(?:\s+|$)").unwrap();
+    static ref RE_ROMAN: Regex = Regex::new(r"(?i)^(\(?[ivx]{1,5}[\.\)])(?:\s+|$)").unwrap();
+    // A bullet is followed by a space, so "-5°C" and ">90%" stay text
+    static ref RE_BULLET: Regex = Regex::new(r"^([\-–—•\*·▪►>])\s+").unwrap();
+    // Units after a decimal number: "1.5 mm grosime" is a value, not section 1.5
+    static ref RE_UNIT: Regex = Regex::new(
+        r"(?i)^(mm|cm|dm|m|km|m2|m²|m3|m³|mp|mc|kg|g|mg|t|l|ml|%|°c?|v|kv|w|kw|kwh|mw|ma|hz|khz|mhz|ghz|kb|mb|gb|tb|mbps|gbps|h|ore|min|s|sec|lei|ron|eur|buc)(?:[\s,;:\.\)]|$)"
+    ).unwrap();
+}
+
+// ─────────────────────────── Markers ───────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+enum Marker {
+    /// Outline number, e.g. [1, 2] for "1.2"
+    Decimal(Vec<u32>),
+    /// A list item; items with the same kind continue the list
+    List(ListKind),
+    Heading,
+    Plain,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ListKind {
+    Number,
+    Lower,
+    Upper,
+    LowerRoman,
+    UpperRoman,
+    Bullet(char),
+}
+
+/// A row split into its marker, the numbering it was written with and the bare text.
+struct Parsed {
+    marker: Marker,
+    numbering: Option<String>,
+    name: String,
+}
+
+/// Marker of a row: from the reference column when it holds a numbering, else from
+/// the start of the text (which is then stripped from the name).
+fn parse_row(row: &OutlineRow, open: &[Open]) -> Parsed {
+    let text = row.text.trim();
+    let reference = row.reference.as_deref().map(str::trim).filter(|r| !r.is_empty());
+
+    if let Some(reference) = reference {
+        if let Some(marker) = reference_marker(reference, open) {
+            // The text may repeat the reference
+            let name = text
+                .strip_prefix(reference)
+                .map(str::trim_start)
+                .filter(|n| !n.is_empty())
+                .unwrap_or(text);
+            return Parsed { marker, numbering: Some(reference.to_string()), name: name.to_string() };
+        }
+    }
+
+    let mut parsed = match parse_marker(text, open) {
+        Some((marker, len)) if !text[len..].trim().is_empty() => Parsed {
+            numbering: match marker {
+                Marker::List(ListKind::Bullet(_)) => None,
+                _ => Some(text[..len].trim().to_string()),
+            },
+            marker,
+            name: text[len..].trim().to_string(),
+        },
+        _ => Parsed { marker: plain(row), numbering: None, name: text.to_string() },
+    };
+    // A reference that is not an outline number ("CT-01") is still the item's reference
+    if let Some(reference) = reference {
+        parsed.numbering = Some(reference.to_string());
+    }
+    parsed
+}
+
+/// Marker of a reference cell holding nothing but a numbering ("1", "1.2", "b)").
+fn reference_marker(reference: &str, open: &[Open]) -> Option<Marker> {
+    if RE_BARE_NUMBER.is_match(reference) {
+        return Some(Marker::Decimal(outline_parts(reference)));
+    }
+    match parse_marker(reference, open) {
+        Some((Marker::List(ListKind::Bullet(_)), _)) => None,
+        Some((marker, len)) if len == reference.len() => Some(marker),
+        _ => None,
+    }
+}
+
+fn plain(row: &OutlineRow) -> Marker {
+    if row.bold {
+        Marker::Heading
+    } else {
+        Marker::Plain
+    }
+}
+
+/// The text without its leading numbering or list marker.
+pub(super) fn strip_marker(text: &str) -> &str {
+    let text = text.trim();
+    match parse_marker(text, &[]) {
+        Some((_, len)) => text[len..].trim(),
+        None => text,
+    }
+}
+
+/// The marker at the start of `text` and the length of the matched prefix.
+fn parse_marker(text: &str, open: &[Open]) -> Option<(Marker, usize)> {
+    if let Some(m) = RE_DECIMAL.captures(text) {
+        if !is_quantity(&m[1], &text[m[0].len()..]) {
+            return Some((Marker::Decimal(outline_parts(&m[1])), m[0].len()));
+        }
+    }
+    if let Some(m) = RE_NUMBER_PAREN.find(text) {
+        return Some((Marker::List(ListKind::Number), m.end()));
+    }
+    if let Some(m) = RE_ROMAN.captures(text) {
+        let token = m[1].trim_start_matches('(');
+        let numeral = &token[..token.len() - 1];
+        if numeral.len() > 1 || !continues_letters(numeral, open) {
+            let kind = if numeral.chars().all(|c| c.is_lowercase()) {
+                ListKind::LowerRoman
+            } else {
+                ListKind::UpperRoman
+            };
+            return Some((Marker::List(kind), m[0].len()));
+        }
+    }
+    if let Some(m) = RE_LETTER.captures(text) {
+        let letter = m[1].trim_start_matches('(').chars().next().unwrap_or('a');
+        let kind = if letter.is_lowercase() { ListKind::Lower } else { ListKind::Upper };
+        return Some((Marker::List(kind), m[0].len()));
+    }
+    if let Some(m) = RE_BULLET.captures(text) {
+        let bullet = m[1].chars().next().unwrap_or('-');
+        return Some((Marker::List(ListKind::Bullet(bullet)), m[0].len()));
+    }
+    None
+}
+
+/// "1.5" followed by a unit is a measurement, not an outline number.
+fn is_quantity(number: &str, rest: &str) -> bool {
+    number.matches('.').count() == 1 && !number.ends_with('.') && RE_UNIT.is_match(rest)
+}
+
+/// "1.2." → [1, 2]
+fn outline_parts(number: &str) -> Vec<u32> {
+    number
+        .trim_end_matches('.')
+        .split('.')
+        .map(|p| p.parse().unwrap_or(0))
+        .collect()
+}
+
+/// "i", "v" or "x" right after "h)", "u)" or "w)" of an open letter list is a letter.
+fn continues_letters(numeral: &str, open: &[Open]) -> bool {
+    let Some(c) = numeral.chars().next() else { return false };
+    let previous = (c.to_ascii_lowercase() as u8 - 1) as char;
+    open.iter().any(|o| {
+        matches!(o.marker, Marker::List(ListKind::Lower | ListKind::Upper)) && o.last_letter == Some(previous)
+    })
+}
+
+// ─────────────────────────── Tree ───────────────────────────
+
+/// A row that can still receive children.
+struct Open {
+    marker: Marker,
+    indent: u32,
+    /// Letter of a letter list item, to tell "i)" after "h)" from a roman numeral
+    last_letter: Option<char>,
+}
+
+/// Build the requirement tree (any depth) from the rows in sheet order.
+pub fn build_tree(rows: Vec<OutlineRow>) -> Vec<ChecklistItemNested> {
+    let mut roots: Vec<ChecklistItemNested> = Vec::new();
+    // Open rows from the top level down, with the child index of each inside its parent
+    let mut open: Vec<Open> = Vec::new();
+    let mut path: Vec<usize> = Vec::new();
+
+    for row in rows {
+        let parsed = parse_row(&row, &open);
+        let depth = parent_depth(&open, &parsed.marker, row.indent);
+        open.truncate(depth);
+        path.truncate(depth);
+
+        let mut item = new_nested_item(&parsed.name);
+        item.reference = parsed.numbering.clone();
+        item.response = row.response;
+        item.document_reference = row.document_reference;
+
+        let siblings = children_at(&mut roots, &path);
+        siblings.push(item);
+        path.push(siblings.len() - 1);
+        open.push(Open {
+            last_letter: match parsed.marker {
+                Marker::List(ListKind::Lower | ListKind::Upper) => parsed
+                    .numbering
+                    .as_deref()
+                    .and_then(|n| n.trim_start_matches('(').chars().next())
+                    .map(|c| c.to_ascii_lowercase()),
+                _ => None,
+            },
+            marker: parsed.marker,
+            indent: row.indent,
+        });
+    }
+
+    roots
+}
+
+/// Number of open rows that stay open: the new row becomes a child of the last of them.
+fn parent_depth(open: &[Open], marker: &Marker, indent: u32) -> usize {
+    // Rows indented deeper than this one are closed whatever their marker
+    let mut depth = open.iter().rposition(|o| o.indent <= indent).map_or(0, |i| i + 1);
+
+    match marker {
+        // Child of its closest numbered ancestor ("1.2" under "1."), or of a heading
+        Marker::Decimal(parts) => {
+            while depth > 0 {
+                match &open[depth - 1].marker {
+                    Marker::Heading => break,
+                    Marker::Decimal(p) if p.len() < parts.len() && parts.starts_with(p) => break,
+                    _ => depth -= 1,
+                }
+            }
+        }
+        // Next item of an open list at the same indentation, else a nested list
+        Marker::List(kind) => {
+            let same_list = open[..depth]
+                .iter()
+                .rposition(|o| o.marker == Marker::List(*kind) && o.indent == indent);
+            if let Some(i) = same_list {
+                depth = i;
+            }
+        }
+        // Child of a heading with less indentation, else a top-level section
+        Marker::Heading => {
+            while depth > 0 && !(open[depth - 1].marker == Marker::Heading && open[depth - 1].indent < indent) {
+                depth -= 1;
+            }
+        }
+        // Child of a less indented row or of the open heading
+        Marker::Plain => {
+            while depth > 0 && open[depth - 1].marker != Marker::Heading && open[depth - 1].indent >= indent {
+                depth -= 1;
+            }
+        }
+    }
+
+    depth
+}
+
+fn children_at<'a>(roots: &'a mut Vec<ChecklistItemNested>, path: &[usize]) -> &'a mut Vec<ChecklistItemNested> {
+    path.iter().fold(roots, |items, &i| &mut items[i].sub_tasks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(text: &str) -> OutlineRow {
+        OutlineRow {
+            row: 0,
+            reference: None,
+            text: text.to_string(),
+            indent: 0,
+            bold: false,
+            response: None,
+            document_reference: None,
+        }
+    }
+
+    fn indented(text: &str, indent: u32) -> OutlineRow {
+        OutlineRow { indent, ..row(text) }
+    }
+
+    fn names(items: &[ChecklistItemNested]) -> Vec<&str> {
+        items.iter().map(|i| i.name.as_str()).collect()
+    }
+
+    #[test]
+    fn decimal_numbers_nest_by_level() {
+        let tree = build_tree(vec![
+            row("1. Cerințe generale"),
+            row("1.1 Alimentare"),
+            row("1.1.1 Tensiune 230V"),
+            row("1.2 Carcasă"),
+            row("2. Garanție"),
+        ]);
+
+        assert_eq!(names(&tree), ["Cerințe generale", "Garanție"]);
+        assert_eq!(tree[0].reference.as_deref(), Some("1."));
+        assert_eq!(names(&tree[0].sub_tasks), ["Alimentare", "Carcasă"]);
+        assert_eq!(tree[0].sub_tasks[0].reference.as_deref(), Some("1.1"));
+        assert_eq!(names(&tree[0].sub_tasks[0].sub_tasks), ["Tensiune 230V"]);
+    }
+
+    #[test]
+    fn lists_nest_under_the_row_above_and_continue() {
+        let tree = build_tree(vec![
+            row("1. Echipament"),
+            row("a) procesor"),
+            row("i. minim 8 nuclee"),
+            row("ii. frecvență 3 GHz"),
+            row("b) memorie"),
+            row("2. Livrare"),
+        ]);
+
+        assert_eq!(names(&tree), ["Echipament", "Livrare"]);
+        let letters = &tree[0].sub_tasks;
+        assert_eq!(names(letters), ["procesor", "memorie"]);
+        assert_eq!(names(&letters[0].sub_tasks), ["minim 8 nuclee", "frecvență 3 GHz"]);
+    }
+
+    #[test]
+    fn letter_i_after_h_continues_the_letter_list() {
+        let tree = build_tree(vec![row("Cerințe"), row("h) opt"), row("i) nouă")]);
+
+        assert_eq!(names(&tree[0].sub_tasks), ["opt", "nouă"]);
+        assert_eq!(tree[0].sub_tasks[1].reference.as_deref(), Some("i)"));
+    }
+
+    #[test]
+    fn bullets_need_a_space() {
+        let tree = build_tree(vec![
+            row("Condiții de funcționare"),
+            indented("- temperatură", 1),
+            indented("-5°C temperatura minimă", 1),
+            indented(">90% disponibilitate", 1),
+        ]);
+
+        assert_eq!(names(&tree), ["Condiții de funcționare"]);
+        let items = &tree[0].sub_tasks;
+        assert_eq!(names(items), ["temperatură", "-5°C temperatura minimă", ">90% disponibilitate"]);
+        assert_eq!(items[1].reference, None);
+    }
+
+    #[test]
+    fn decimal_with_a_unit_is_not_numbering() {
+        let tree = build_tree(vec![
+            row("1. Panouri"),
+            indented("1.5 mm grosime", 1),
+            row("1.2 Culoare"),
+        ]);
+
+        assert_eq!(names(&tree), ["Panouri"]);
+        assert_eq!(names(&tree[0].sub_tasks), ["1.5 mm grosime", "Culoare"]);
+        assert_eq!(tree[0].sub_tasks[0].reference, None);
+    }
+
+    #[test]
+    fn bold_rows_are_headings_and_indented_rows_nest() {
+        let tree = build_tree(vec![
+            OutlineRow { bold: true, ..row("Secțiunea A") },
+            row("Cerință"),
+            indented("Detaliu", 1),
+            OutlineRow { bold: true, ..row("Secțiunea B") },
+            row("Altă cerință"),
+        ]);
+
+        assert_eq!(names(&tree), ["Secțiunea A", "Secțiunea B"]);
+        assert_eq!(names(&tree[0].sub_tasks), ["Cerință"]);
+        assert_eq!(names(&tree[0].sub_tasks[0].sub_tasks), ["Detaliu"]);
+        assert_eq!(names(&tree[1].sub_tasks), ["Altă cerință"]);
+    }
+
+    #[test]
+    fn reference_column_gives_the_numbering() {
+        let tree = build_tree(vec![
+            OutlineRow { reference: Some("1".into()), ..row("Generale") },
+            OutlineRow { reference: Some("1.1".into()), ..row("1.1 Alimentare") },
+            OutlineRow { reference: Some("CT-01".into()), ..row("Certificat CE") },
+        ]);
+
+        assert_eq!(names(&tree), ["Generale", "Certificat CE"]);
+        assert_eq!(names(&tree[0].sub_tasks), ["Alimentare"]);
+        assert_eq!(tree[1].reference.as_deref(), Some("CT-01"));
+    }
+
+    #[test]
+    fn strip_marker_keeps_quantities() {
+        assert_eq!(strip_marker("1.2. Alimentare"), "Alimentare");
+        assert_eq!(strip_marker("b) memorie"), "memorie");
+        assert_eq!(strip_marker("• garanție"), "garanție");
+        assert_eq!(strip_marker("2.5 kg masă"), "2.5 kg masă");
+        assert_eq!(strip_marker("-5°C"), "-5°C");
+    }
+}
@@ -0,0 +1,219 @@
+//! What an import could not take as is (see `ImportReport`): rows with content but no
+//! requirement, numbers and dates read as text, merged cells, repeated requirements and
+//! cells long enough to hold several requirements.
+
+use std::collections::HashMap;
+
+use calamine::{DataType, Range};
+
+use super::outline::{strip_marker, OutlineRow};
+use super::{cell_text, to_position, words};
+use crate::models::{ImportIssue, ImportReport, IssueKind};
+
+/// A requirement longer than this, or with this many lines, is reported.
+const LONG_CELL_CHARS: usize = 600;
+const LONG_CELL_LINES: usize = 5;
+/// Cell text quoted in messages is cut to this many characters.
+const EXCERPT_CHARS: usize = 60;
+
+pub(super) fn add(report: &mut ImportReport, kind: IssueKind, row: u32, col: Option<u32>, message: String) {
+    report.issues.push(ImportIssue {
+        kind,
+        row: to_position(row),
+        column: col.map(to_position),
+        message,
+    });
+}
+
+/// Report a number, date or boolean cell that was taken as text.
+pub(super) fn converted(report: &mut ImportReport, range: &Range<DataType>, row: u32, col: u32) {
+    let what = match range.get_value((row, col)) {
+        Some(DataType::Int(_) | DataType::Float(_)) => "Număr preluat",
+        Some(DataType::DateTime(_) | DataType::DateTimeIso(_)) => "Dată preluată",
+        Some(DataType::Duration(_) | DataType::DurationIso(_)) => "Durată preluată",
+        Some(DataType::Bool(_)) => "Valoare logică preluată",
+        _ => return,
+    };
+    let message = format!("{} ca text: „{}”", what, excerpt(&cell_text(range, row, col)));
+    add(report, IssueKind::ConvertedCell, row, Some(col), message);
+}
+
+/// Import stopped at the end marker on `row`: the rows with content from there on are lost.
+pub(super) fn end_marker(report: &mut ImportReport, range: &Range<DataType>, row: u32) {
+    let (Some(start), Some(end)) = (range.start(), range.end()) else { return };
+    let ignored = (row..=end.0)
+        .filter(|&r| (start.1..=end.1).any(|c| !cell_text(range, r, c).is_empty()))
+        .count();
+    let message = format!("Import oprit la marcajul de final; {} rânduri cu conținut ignorate", ignored);
+    add(report, IssueKind::SkippedRow, row, None, message);
+}
+
+/// Repeated requirements and overly long cells among the imported rows, then every
+/// issue in sheet order.
+pub(super) fn review_rows(report: &mut ImportReport, rows: &[OutlineRow], name_col: u32) {
+    let mut first_rows: HashMap<String, u32> = HashMap::new();
+    for row in rows {
+        let key = words(strip_marker(&row.text)).collect::<Vec<_>>().join(" ");
+        if key.is_empty() {
+            continue;
+        }
+        match first_rows.get(&key) {
+            Some(&first) => {
+                let message = format!(
+                    "Același text ca cerința de pe rândul {}: „{}”",
+                    to_position(first),
+                    excerpt(&row.text)
+                );
+                add(report, IssueKind::DuplicateName, row.row, Some(name_col), message);
+            }
+            None => {
+                first_rows.insert(key, row.row);
+            }
+        }
+
+        let chars = row.text.chars().count();
+        let lines = row.text.lines().filter(|l| !l.trim().is_empty()).count();
+        if chars > LONG_CELL_CHARS || lines >= LONG_CELL_LINES {
+            let message = format!(
+                "Celulă foarte lungă ({} caractere, {} rânduri): poate conține mai multe cerințe",
+                chars, lines
+            );
+            add(report, IssueKind::LongCell, row.row, Some(name_col), message);
+        }
+    }
+
+    report.issues.sort_by_key(|i| i.row);
+}
+
+/// "B7" for 0-based (6, 1).
+pub(super) fn cell_ref(row: u32, col: u32) -> String {
+    let mut letters = String::new();
+    let mut n = col + 1;
+    while n > 0 {
+        letters.insert(0, (b'A' + ((n - 1) % 26) as u8) as char);
+        n = (n - 1) / 26;
+    }
+    format!("{}{}", letters, row + 1)
+}
+
+pub(super) fn excerpt(text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() <= EXCERPT_CHARS {
+        return text;
+    }
+    format!("{}…", text.chars().take(EXCERPT_CHARS).collect::<String>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::excel::requirement_cell;
+    use crate::excel::styles::CellStyles;
+
+    fn row(row: u32, text: &str) -> OutlineRow {
+        OutlineRow {
+            row,
+            reference: None,
+            text: text.to_string(),
+            indent: 0,
+            bold: false,
+            response: None,
+            document_reference: None,
+        }
+    }
+
+    fn kinds(report: &ImportReport) -> Vec<(IssueKind, i32)> {
+        report.issues.iter().map(|i| (i.kind, i.row)).collect()
+    }
+
+    #[test]
+    fn repeated_requirements_point_to_the_first_row() {
+        let mut report = ImportReport::default();
+        let rows = [
+            row(1, "1.1 Garanție 36 luni"),
+            row(2, "Alimentare 230 V"),
+            // Same words once the numbering, case and punctuation are set aside
+            row(4, "2.3  garanție 36 LUNI."),
+            row(5, "Garanție 24 luni"),
+        ];
+        review_rows(&mut report, &rows, 1);
+
+        assert_eq!(kinds(&report), [(IssueKind::DuplicateName, 5)]);
+        let issue = &report.issues[0];
+        assert_eq!(issue.column, Some(2));
+        assert!(issue.message.contains("rândul 2"), "{}", issue.message);
+    }
+
+    #[test]
+    fn long_cells_by_length_or_lines() {
+        let mut report = ImportReport::default();
+        let long = "Echipamentul va fi livrat cu toate accesoriile necesare. ".repeat(12);
+        let many_lines = "Laptop\n- procesor\n- memorie\n\n- disc\n- ecran";
+        let rows = [
+            row(3, "Cerință scurtă"),
+            row(1, &long),
+            row(2, many_lines),
+            row(6, "Patru\nrânduri\nsunt\nacceptate"),
+        ];
+        review_rows(&mut report, &rows, 1);
+
+        // Sorted in sheet order
+        assert_eq!(kinds(&report), [(IssueKind::LongCell, 2), (IssueKind::LongCell, 3)]);
+        assert!(report.issues[1].message.contains("5 rânduri"), "{}", report.issues[1].message);
+    }
+
+    #[test]
+    fn merged_requirement_cells() {
+        let mut range = Range::new((0, 0), (3, 3));
+        range.set_value((0, 0), DataType::String("Secțiunea A".into()));
+        range.set_value((1, 1), DataType::String("Laptop 15 inch".into()));
+        let mut styles = CellStyles::default();
+        // A1:B1 across the reference column, B2:B4 down three rows, C2:D2 left empty
+        styles.merge((0, 0), (0, 1));
+        styles.merge((1, 1), (3, 1));
+        styles.merge((1, 2), (1, 3));
+        let mut report = ImportReport::default();
+
+        assert_eq!(requirement_cell(&range, &styles, 0, 1, &mut report).as_deref(), Some("Secțiunea A"));
+        assert_eq!(requirement_cell(&range, &styles, 1, 1, &mut report).as_deref(), Some("Laptop 15 inch"));
+        assert_eq!(requirement_cell(&range, &styles, 2, 1, &mut report), None);
+        assert_eq!(requirement_cell(&range, &styles, 1, 3, &mut report), None);
+
+        let issues: Vec<_> = report.issues.iter().map(|i| (i.kind, i.row, i.column)).collect();
+        assert_eq!(
+            issues,
+            [
+                (IssueKind::MergedCell, 1, Some(2)),
+                (IssueKind::MergedCell, 3, Some(2)),
+                (IssueKind::SkippedRow, 2, Some(4)),
+            ]
+        );
+        assert!(report.issues[0].message.contains("A1"), "{}", report.issues[0].message);
+        assert!(report.issues[1].message.contains("rândul 2"), "{}", report.issues[1].message);
+    }
+
+    #[test]
+    fn dates_outside_the_calendar_keep_their_number() {
+        let mut range = Range::new((0, 0), (0, 1));
+        range.set_value((0, 0), DataType::DateTime(46_082.5));
+        range.set_value((0, 1), DataType::DateTime(1e20));
+        let mut report = ImportReport::default();
+        converted(&mut report, &range, 0, 0);
+        converted(&mut report, &range, 0, 1);
+
+        let messages: Vec<_> = report.issues.iter().map(|i| i.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            ["Dată preluată ca text: „01.03.2026 12:00”", "Dată preluată ca text: „100000000000000000000”"]
+        );
+    }
+
+    #[test]
+    fn cell_references_past_z() {
+        assert_eq!(cell_ref(6, 1), "B7");
+        assert_eq!(cell_ref(0, 25), "Z1");
+        assert_eq!(cell_ref(9, 26), "AA10");
+        assert_eq!(cell_ref(0, 701), "ZZ1");
+        assert_eq!(cell_ref(0, 702), "AAA1");
+    }
+}
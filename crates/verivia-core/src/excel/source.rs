@@ -0,0 +1,325 @@
+//! Documents the requirements can be imported from. Every source exposes its tables as
+//! calamine ranges, so layout detection and the outline work the same for all of them.
+//!
+//! - spreadsheets (xlsx, xlsm, xlsb, xls, ods) through calamine, one table per sheet
+//! - CSV, a single table named after the file
+//! - DOCX, one table per top-level Word table ("Tabel 1", "Tabel 2", ...)
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+use calamine::{open_workbook_auto, Cell, DataType, Range, Reader, Sheets};
+use quick_xml::events::Event;
+use quick_xml::Reader as XmlReader;
+use zip::ZipArchive;
+
+use super::styles::{self, CellStyles};
+use crate::errors::{AppError, Result};
+
+/// A document holding one or more requirement tables.
+pub trait ImportSource {
+    /// Table names, in document order.
+    fn sheet_names(&self) -> Vec<String>;
+
+    /// Cells of the table named `name`.
+    fn sheet(&mut self, name: &str) -> Result<Range<DataType>>;
+
+    /// Indentation and bold text of the table's cells, where the format has them.
+    fn styles(&self, _name: &str) -> CellStyles {
+        CellStyles::default()
+    }
+}
+
+/// Open `file_path` with the source matching its extension.
+pub fn open_source(file_path: &str) -> Result<Box<dyn ImportSource>> {
+    let extension = Path::new(file_path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase)
+        .unwrap_or_default();
+
+    match extension.as_str() {
+        "csv" | "txt" => Ok(Box::new(CsvSource::open(file_path)?)),
+        "docx" => Ok(Box::new(DocxSource::open(file_path)?)),
+        _ => Ok(Box::new(SpreadsheetSource::open(file_path)?)),
+    }
+}
+
+// ─────────────────────────── Spreadsheets ───────────────────────────
+
+struct SpreadsheetSource {
+    path: String,
+    workbook: Sheets<BufReader<File>>,
+}
+
+impl SpreadsheetSource {
+    fn open(file_path: &str) -> Result<Self> {
+        let workbook = open_workbook_auto(file_path).map_err(|e| AppError::Excel(e.to_string()))?;
+        Ok(Self { path: file_path.to_string(), workbook })
+    }
+}
+
+impl ImportSource for SpreadsheetSource {
+    fn sheet_names(&self) -> Vec<String> {
+        self.workbook.sheet_names().to_vec()
+    }
+
+    fn sheet(&mut self, name: &str) -> Result<Range<DataType>> {
+        self.workbook
+            .worksheet_range(name)
+            .ok_or_else(|| AppError::Excel("Cannot read range".into()))?
+            .map_err(|e| AppError::Excel(e.to_string()))
+    }
+
+    fn styles(&self, name: &str) -> CellStyles {
+        styles::read(&self.path, name)
+    }
+}
+
+// ─────────────────────────── CSV ───────────────────────────
+
+struct CsvSource {
+    name: String,
+    range: Range<DataType>,
+}
+
+impl CsvSource {
+    fn open(file_path: &str) -> Result<Self> {
+        let bytes = std::fs::read(file_path).map_err(|e| AppError::Excel(e.to_string()))?;
+        let text = decode(&bytes);
+
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .delimiter(sniff_delimiter(&text))
+            .from_reader(text.as_bytes());
+
+        let mut cells = Vec::new();
+        for (row, record) in reader.records().enumerate() {
+            let record = record.map_err(|e| AppError::Excel(format!("Invalid CSV: {}", e)))?;
+            for (col, value) in record.iter().enumerate() {
+                if !value.trim().is_empty() {
+                    cells.push(Cell::new((row as u32, col as u32), DataType::String(value.to_string())));
+                }
+            }
+        }
+
+        let name = Path::new(file_path)
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("CSV")
+            .to_string();
+        Ok(Self { name, range: Range::from_sparse(cells) })
+    }
+}
+
+impl ImportSource for CsvSource {
+    fn sheet_names(&self) -> Vec<String> {
+        vec![self.name.clone()]
+    }
+
+    fn sheet(&mut self, name: &str) -> Result<Range<DataType>> {
+        if !name.trim().eq_ignore_ascii_case(self.name.trim()) {
+            return Err(AppError::Excel(format!("Sheet '{}' not found", name)));
+        }
+        Ok(self.range.clone())
+    }
+}
+
+/// UTF-8 (BOM removed), else Windows-1250 as saved by Romanian Excel.
+fn decode(bytes: &[u8]) -> String {
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) => encoding_rs::WINDOWS_1250.decode(bytes).0.into_owned(),
+    }
+}
+
+/// The most frequent of `;`, tab and `,` on the first lines (Excel uses `;` with a
+/// decimal comma).
+fn sniff_delimiter(text: &str) -> u8 {
+    let sample: Vec<&str> = text.lines().take(20).collect();
+    [b';', b'\t', b',']
+        .into_iter()
+        .max_by_key(|&d| sample.iter().map(|l| l.bytes().filter(|&b| b == d).count()).sum::<usize>())
+        .unwrap_or(b',')
+}
+
+// ─────────────────────────── DOCX ───────────────────────────
+
+struct DocxSource {
+    tables: Vec<(Range<DataType>, CellStyles)>,
+}
+
+impl DocxSource {
+    fn open(file_path: &str) -> Result<Self> {
+        let file = File::open(file_path).map_err(|e| AppError::Excel(e.to_string()))?;
+        let mut archive = ZipArchive::new(file).map_err(|e| AppError::Excel(e.to_string()))?;
+        let mut xml = String::new();
+        archive
+            .by_name("word/document.xml")
+            .map_err(|e| AppError::Excel(format!("Not a Word document: {}", e)))?
+            .read_to_string(&mut xml)
+            .map_err(|e| AppError::Excel(e.to_string()))?;
+
+        Ok(Self { tables: read_tables(&xml)? })
+    }
+}
+
+impl ImportSource for DocxSource {
+    fn sheet_names(&self) -> Vec<String> {
+        (1..=self.tables.len()).map(table_name).collect()
+    }
+
+    fn sheet(&mut self, name: &str) -> Result<Range<DataType>> {
+        table_index(name)
+            .and_then(|i| self.tables.get(i))
+            .map(|(range, _)| range.clone())
+            .ok_or_else(|| AppError::Excel(format!("Sheet '{}' not found", name)))
+    }
+
+    fn styles(&self, name: &str) -> CellStyles {
+        table_index(name)
+            .and_then(|i| self.tables.get(i))
+            .map(|(_, styles)| styles.clone())
+            .unwrap_or_default()
+    }
+}
+
+fn table_name(position: usize) -> String {
+    format!("Tabel {}", position)
+}
+
+fn table_index(name: &str) -> Option<usize> {
+    name.trim().strip_prefix("Tabel ")?.parse::<usize>().ok()?.checked_sub(1)
+}
+
+/// A cell being read: its text and whether every run so far is bold.
+#[derive(Default)]
+struct DocxCell {
+    paragraphs: Vec<String>,
+    indent: u32,
+    bold: Option<bool>,
+    /// `vMerge`: Some(true) starts a vertically merged cell, Some(false) continues it
+    vertical_merge: Option<bool>,
+}
+
+/// Top-level tables of `word/document.xml` (tables nested in a cell are read as text of
+/// that cell). Merged cells (`gridSpan`, `vMerge`) keep the column positions of the grid
+/// and are recorded as merged regions.
+fn read_tables(xml: &str) -> Result<Vec<(Range<DataType>, CellStyles)>> {
+    let mut reader = XmlReader::from_str(xml);
+    let mut tables = Vec::new();
+
+    let mut depth = 0; // table nesting
+    let mut cells: Vec<Cell<DataType>> = Vec::new();
+    let mut styles = CellStyles::default();
+    let (mut row, mut col) = (0u32, 0u32);
+    let mut span = 1;
+    // Row where the vertical merge of each column started
+    let mut merge_starts: HashMap<u32, u32> = HashMap::new();
+    let mut cell: Option<DocxCell> = None;
+    let mut paragraph = String::new();
+    let mut run_bold = false;
+    let mut in_text = false;
+
+    loop {
+        let event = reader.read_event().map_err(|e| AppError::Excel(format!("Invalid Word document: {}", e)))?;
+        match event {
+            Event::Start(e) if depth > 0 && e.local_name().as_ref() == b"t" => in_text = true,
+            Event::Start(e) | Event::Empty(e) if depth > 0 => match e.local_name().as_ref() {
+                b"tbl" => depth += 1,
+                b"tr" if depth == 1 => col = 0,
+                b"tc" if depth == 1 => {
+                    cell = Some(DocxCell::default());
+                    span = 1;
+                }
+                b"gridSpan" if depth == 1 => span = val(&e).and_then(|v| v.parse().ok()).unwrap_or(1),
+                b"vMerge" if depth == 1 => {
+                    if let Some(c) = cell.as_mut() {
+                        c.vertical_merge = Some(val(&e).as_deref() == Some("restart"));
+                    }
+                }
+                b"p" => paragraph.clear(),
+                b"ilvl" => {
+                    if let (Some(c), Some(level)) = (cell.as_mut(), val(&e).and_then(|v| v.parse().ok())) {
+                        c.indent = c.indent.max(level);
+                    }
+                }
+                b"r" => run_bold = false,
+                b"b" => run_bold = val(&e).is_none_or(|v| v != "0" && v != "false"),
+                b"tab" => paragraph.push(' '),
+                _ => {}
+            },
+            Event::Start(e) if e.local_name().as_ref() == b"tbl" => {
+                depth = 1;
+                cells.clear();
+                styles = CellStyles::default();
+                merge_starts.clear();
+                row = 0;
+            }
+            Event::Text(t) if in_text => {
+                let text = t.unescape().map_err(|e| AppError::Excel(e.to_string()))?;
+                if let Some(c) = cell.as_mut().filter(|_| !text.trim().is_empty()) {
+                    c.bold = Some(c.bold.unwrap_or(true) && run_bold);
+                }
+                paragraph.push_str(&text);
+            }
+            Event::End(e) if depth > 0 => match e.local_name().as_ref() {
+                b"t" => in_text = false,
+                b"p" => {
+                    if let Some(c) = cell.as_mut().filter(|_| !paragraph.trim().is_empty()) {
+                        c.paragraphs.push(paragraph.trim().to_string());
+                    }
+                    paragraph.clear();
+                }
+                b"tc" if depth == 1 => {
+                    if let Some(c) = cell.take() {
+                        let text = c.paragraphs.join(" ");
+                        if !text.is_empty() {
+                            cells.push(Cell::new((row, col), DataType::String(text)));
+                            styles.set((row, col), c.indent, c.bold.unwrap_or(false));
+                        }
+                        let last_col = col + span.max(1) - 1;
+                        if c.vertical_merge == Some(false) {
+                            let start = merge_starts.get(&col).copied().unwrap_or(row);
+                            styles.merge((start, col), (row, last_col));
+                        } else {
+                            if c.vertical_merge == Some(true) {
+                                merge_starts.insert(col, row);
+                            } else {
+                                merge_starts.remove(&col);
+                            }
+                            if last_col > col {
+                                styles.merge((row, col), (row, last_col));
+                            }
+                        }
+                    }
+                    col += span;
+                }
+                b"tr" if depth == 1 => row += 1,
+                b"tbl" => {
+                    depth -= 1;
+                    if depth == 0 {
+                        tables.push((Range::from_sparse(std::mem::take(&mut cells)), std::mem::take(&mut styles)));
+                    }
+                }
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(tables)
+}
+
+/// The `w:val` attribute of a Word element.
+fn val(e: &quick_xml::events::BytesStart) -> Option<String> {
+    e.attributes()
+        .flatten()
+        .find(|a| a.key.local_name().as_ref() == b"val")
+        .and_then(|a| String::from_utf8(a.value.into_owned()).ok())
+}
@@ -0,0 +1,240 @@
+//! Cell indentation, bold font and merged cells of an xlsx sheet. calamine only reads
+//! values, so the formatting is read straight from the workbook XML. Formatting is only a
+//! hint for the outline: any other format, or a file that cannot be read, yields none.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+
+use quick_xml::escape::unescape;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader as XmlReader;
+use zip::ZipArchive;
+
+/// Larger merged regions (whole columns, page banners) are not requirement cells.
+const MAX_MERGED_CELLS: u64 = 100_000;
+
+/// Formatting of the cells that have any, by 0-based (row, column).
+#[derive(Debug, Default, Clone)]
+pub struct CellStyles {
+    cells: HashMap<(u32, u32), CellStyle>,
+    /// Cells covered by a merged region (all but its top-left cell) → top-left cell
+    merged: HashMap<(u32, u32), (u32, u32)>,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct CellStyle {
+    indent: u32,
+    bold: bool,
+}
+
+impl CellStyles {
+    pub fn indent(&self, row: u32, col: u32) -> u32 {
+        self.cells.get(&(row, col)).map_or(0, |s| s.indent)
+    }
+
+    pub fn bold(&self, row: u32, col: u32) -> bool {
+        self.cells.get(&(row, col)).is_some_and(|s| s.bold)
+    }
+
+    /// Top-left cell of the merged region covering `(row, col)`, which then has no
+    /// value of its own; `None` for the top-left cell itself and unmerged cells.
+    pub fn merged_origin(&self, row: u32, col: u32) -> Option<(u32, u32)> {
+        self.merged.get(&(row, col)).copied()
+    }
+
+    /// Record the formatting of a cell (sources other than xlsx, see `source.rs`).
+    pub(super) fn set(&mut self, cell: (u32, u32), indent: u32, bold: bool) {
+        if indent > 0 || bold {
+            self.cells.insert(cell, CellStyle { indent, bold });
+        }
+    }
+
+    /// Record a merged region from its top-left to its bottom-right cell.
+    pub(super) fn merge(&mut self, from: (u32, u32), to: (u32, u32)) {
+        let size = (to.0.saturating_sub(from.0) as u64 + 1) * (to.1.saturating_sub(from.1) as u64 + 1);
+        if size > MAX_MERGED_CELLS {
+            return;
+        }
+        for row in from.0..=to.0 {
+            for col in from.1..=to.1 {
+                if (row, col) != from {
+                    self.merged.insert((row, col), from);
+                }
+            }
+        }
+    }
+}
+
+/// Formatting of `sheet_name` in the workbook at `file_path`.
+pub fn read(file_path: &str, sheet_name: &str) -> CellStyles {
+    let is_xlsx = [".xlsx", ".xlsm"]
+        .iter()
+        .any(|ext| file_path.to_lowercase().ends_with(ext));
+    if !is_xlsx {
+        return CellStyles::default();
+    }
+    read_xlsx(file_path, sheet_name).unwrap_or_default()
+}
+
+// ─────────────────────────── Workbook parts ───────────────────────────
+
+type Archive = ZipArchive<File>;
+
+fn read_xlsx(file_path: &str, sheet_name: &str) -> Option<CellStyles> {
+    let mut archive = ZipArchive::new(File::open(file_path).ok()?).ok()?;
+    let sheet_path = sheet_path(&mut archive, sheet_name)?;
+    let formats = cell_formats(&mut archive)?;
+
+    let mut styles = CellStyles::default();
+    for_each_element(&part(&mut archive, &sheet_path)?, |e, _| match e.local_name().as_ref() {
+        b"c" => {
+            let style = attr(e, b"s").and_then(|s| s.parse::<usize>().ok());
+            let cell = attr(e, b"r").and_then(|r| parse_cell_ref(&r));
+            if let (Some(style), Some(cell)) = (style, cell) {
+                match formats.get(style) {
+                    Some(&s) if s.indent > 0 || s.bold => {
+                        styles.cells.insert(cell, s);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        // <mergeCell ref="A3:D3"/>
+        b"mergeCell" => {
+            let region = attr(e, b"ref").and_then(|r| {
+                let (from, to) = r.split_once(':')?;
+                Some((parse_cell_ref(from)?, parse_cell_ref(to)?))
+            });
+            if let Some((from, to)) = region {
+                styles.merge(from, to);
+            }
+        }
+        _ => {}
+    });
+
+    Some(styles)
+}
+
+/// Path inside the archive of the sheet named `sheet_name` (workbook → relationships).
+fn sheet_path(archive: &mut Archive, sheet_name: &str) -> Option<String> {
+    let mut rel_id = None;
+    for_each_element(&part(archive, "xl/workbook.xml")?, |e, _| {
+        if e.local_name().as_ref() == b"sheet"
+            && attr(e, b"name").is_some_and(|n| n.trim().eq_ignore_ascii_case(sheet_name.trim()))
+        {
+            rel_id = attr(e, b"id");
+        }
+    });
+    let rel_id = rel_id?;
+
+    let mut target = None;
+    for_each_element(&part(archive, "xl/_rels/workbook.xml.rels")?, |e, _| {
+        if e.local_name().as_ref() == b"Relationship" && attr(e, b"Id").as_deref() == Some(rel_id.as_str()) {
+            target = attr(e, b"Target");
+        }
+    });
+
+    target.map(|t| match t.strip_prefix('/') {
+        Some(absolute) => absolute.to_string(),
+        None => format!("xl/{}", t),
+    })
+}
+
+/// Indent and boldness of every cell format (`cellXfs`), by style index.
+fn cell_formats(archive: &mut Archive) -> Option<Vec<CellStyle>> {
+    let xml = part(archive, "xl/styles.xml")?;
+
+    let mut bold_fonts = Vec::new();
+    let mut font_ids = Vec::new();
+    let mut indents = Vec::new();
+
+    let parsed = for_each_element(&xml, |e, parents| {
+        let inside = |name: &[u8]| parents.iter().any(|p| p.as_slice() == name);
+        match (parents.last().map(Vec::as_slice), e.local_name().as_ref()) {
+            (Some(b"fonts"), b"font") => bold_fonts.push(false),
+            (Some(b"font"), b"b") if inside(b"fonts") => {
+                if let Some(bold) = bold_fonts.last_mut() {
+                    *bold = attr(e, b"val").is_none_or(|v| v != "0" && v != "false");
+                }
+            }
+            (Some(b"cellXfs"), b"xf") => {
+                font_ids.push(attr(e, b"fontId").and_then(|f| f.parse::<usize>().ok()).unwrap_or(0));
+                indents.push(0);
+            }
+            (Some(b"xf"), b"alignment") if inside(b"cellXfs") => {
+                if let Some(indent) = indents.last_mut() {
+                    *indent = attr(e, b"indent").and_then(|i| i.parse().ok()).unwrap_or(0);
+                }
+            }
+            _ => {}
+        }
+    });
+    if !parsed {
+        return None;
+    }
+
+    Some(
+        font_ids
+            .into_iter()
+            .zip(indents)
+            .map(|(font, indent)| CellStyle { indent, bold: bold_fonts.get(font).copied().unwrap_or(false) })
+            .collect(),
+    )
+}
+
+// ─────────────────────────── XML helpers ───────────────────────────
+
+fn part(archive: &mut Archive, path: &str) -> Option<String> {
+    let mut xml = String::new();
+    archive.by_name(path).ok()?.read_to_string(&mut xml).ok()?;
+    Some(xml)
+}
+
+/// Call `f(element, enclosing element names)` for every opening or empty element.
+/// Returns false if the XML is malformed.
+fn for_each_element(xml: &str, mut f: impl FnMut(&BytesStart, &[Vec<u8>])) -> bool {
+    let mut reader = XmlReader::from_str(xml);
+    let mut parents = Vec::new();
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => {
+                f(&e, &parents);
+                parents.push(e.local_name().as_ref().to_vec());
+            }
+            Ok(Event::Empty(e)) => f(&e, &parents),
+            Ok(Event::End(_)) => {
+                parents.pop();
+            }
+            Ok(Event::Eof) => return true,
+            Err(_) => return false,
+            Ok(_) => {}
+        }
+    }
+}
+
+/// Unescaped value of an attribute, matched by local name (prefix ignored, e.g. `r:id`).
+fn attr(e: &BytesStart, name: &[u8]) -> Option<String> {
+    e.attributes()
+        .flatten()
+        .find(|a| a.key.local_name().as_ref() == name)
+        .and_then(|a| {
+            let raw = std::str::from_utf8(&a.value).ok()?;
+            unescape(raw).ok().map(|v| v.into_owned())
+        })
+}
+
+/// "B7" → 0-based (6, 1).
+fn parse_cell_ref(cell: &str) -> Option<(u32, u32)> {
+    let split = cell.find(|c: char| c.is_ascii_digit())?;
+    let (letters, digits) = cell.split_at(split);
+    if letters.is_empty() {
+        return None;
+    }
+    let col = letters.bytes().try_fold(0u32, |acc, b| {
+        b.is_ascii_alphabetic()
+            .then(|| acc * 26 + (b.to_ascii_uppercase() - b'A') as u32 + 1)
+    })?;
+    let row: u32 = digits.parse().ok()?;
+    Some((row.checked_sub(1)?, col - 1))
+}
@@ -0,0 +1,390 @@
+//! Export a project's checklists as an xlsx compliance matrix: a summary sheet, then one
+//! sheet per category with the requirement hierarchy, our responses, flags, attribution,
+//! dates and notes.
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, Local, Utc};
+use rust_xlsxwriter::{Color, Format, FormatAlign, FormatBorder, Workbook, Worksheet, XlsxError};
+use sqlx::PgPool;
+
+use crate::errors::{AppError, Result};
+use crate::models::{CategoryFull, ChecklistExport, ChecklistItemNested, Project, ProjectFull};
+use crate::projects::load_project_trees;
+
+const SUMMARY_SHEET: &str = "Rezumat";
+/// Excel limit on sheet names.
+const MAX_SHEET_NAME: usize = 31;
+const DATE_FORMAT: &str = "dd.mm.yyyy hh:mm";
+/// Excel limit on the text of a cell; longer text is cut and ends with `TRUNCATED`.
+const MAX_CELL_TEXT: usize = 32_767;
+const TRUNCATED: &str = " … [text trunchiat]";
+
+/// Category sheet columns: (title, width).
+const COLUMNS: &[(&str, f64)] = &[
+    ("Nr.", 8.0),
+    ("Cerință", 70.0),
+    ("Răspuns", 50.0),
+    ("Referință document", 22.0),
+    ("Propus", 9.0),
+    ("Propus de", 24.0),
+    ("Data propunerii", 17.0),
+    ("Verificat", 9.0),
+    ("Verificat de", 24.0),
+    ("Data verificării", 17.0),
+    ("Stare", 15.0),
+    ("Note", 60.0),
+];
+
+// ─────────────────────────── Export ───────────────────────────
+
+/// Write the checklist of every category (or only `category_name`) of the project to
+/// the xlsx file at `path`, replacing it if it exists.
+pub async fn export_checklist(
+    pool: &PgPool,
+    project_id: i32,
+    category_name: Option<&str>,
+    path: &str,
+) -> Result<ChecklistExport> {
+    let project = load_project(pool, project_id).await?;
+
+    let categories: Vec<&CategoryFull> = match category_name.map(str::trim) {
+        Some(wanted) => {
+            let category = project
+                .categories
+                .iter()
+                .find(|c| c.name.eq_ignore_ascii_case(wanted))
+                .ok_or_else(|| AppError::NotFound(format!("Category '{}' not found", wanted)))?;
+            vec![category]
+        }
+        None => project.categories.iter().collect(),
+    };
+
+    let items = categories.iter().map(|c| count(&c.checklist).total).sum();
+    write_workbook(&project, &categories, path).map_err(|e| AppError::Excel(e.to_string()))?;
+
+    Ok(ChecklistExport {
+        path: path.to_string(),
+        sheets: categories.len() + 1,
+        items,
+    })
+}
+
+/// File name of an export: "Matrice conformitate - <project>[ - <category>] - <date time>.xlsx",
+/// without characters Windows does not allow in file names. The time keeps earlier
+/// exports in the project folder from being overwritten.
+pub async fn export_file_name(pool: &PgPool, project_id: i32, category_name: Option<&str>) -> Result<String> {
+    let title = sqlx::query_scalar!(
+        "SELECT title FROM projects WHERE id = $1 AND deleted_at IS NULL",
+        project_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Project id={} not found", project_id)))?;
+
+    let stamp = Local::now().format("%Y-%m-%d %H.%M.%S");
+    let name = match category_name.map(str::trim) {
+        Some(category) => format!("Matrice conformitate - {} - {} - {}", title, category, stamp),
+        None => format!("Matrice conformitate - {} - {}", title, stamp),
+    };
+    let safe: String = name
+        .chars()
+        .map(|c| if matches!(c, '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*') { '_' } else { c })
+        .collect();
+    Ok(format!("{}.xlsx", safe.trim_end_matches(['.', ' '])))
+}
+
+async fn load_project(pool: &PgPool, project_id: i32) -> Result<ProjectFull> {
+    let project = sqlx::query_as!(
+        Project,
+        "SELECT id, title, date, year, path, submission_deadline, clarification_deadline, opening_date,
+                contracting_authority, notice_number, cpv_codes, estimated_value, currency,
+                procedure_type, lots, owner, status
+         FROM projects WHERE id = $1 AND deleted_at IS NULL",
+        project_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Project id={} not found", project_id)))?;
+
+    load_project_trees(pool, &[project])
+        .await?
+        .pop()
+        .ok_or_else(|| AppError::NotFound(format!("Project id={} not found", project_id)))
+}
+
+// ─────────────────────────── Workbook ───────────────────────────
+
+struct Formats {
+    title: Format,
+    label: Format,
+    header: Format,
+    text: Format,
+    number: Format,
+    percent: Format,
+    date: Format,
+    total: Format,
+    total_percent: Format,
+}
+
+impl Formats {
+    fn new() -> Self {
+        let cell = Format::new().set_border(FormatBorder::Thin).set_align(FormatAlign::Top);
+        Self {
+            title: Format::new().set_bold().set_font_size(14),
+            label: Format::new().set_bold(),
+            header: cell
+                .clone()
+                .set_bold()
+                .set_text_wrap()
+                .set_background_color(Color::RGB(0xD9E1F2)),
+            text: cell.clone().set_text_wrap(),
+            number: cell.clone(),
+            percent: cell.clone().set_num_format("0%"),
+            date: cell.clone().set_num_format(DATE_FORMAT),
+            total: cell.clone().set_bold(),
+            total_percent: cell.set_bold().set_num_format("0%"),
+        }
+    }
+
+    /// Requirement text indented by its nesting level; parents in bold.
+    fn requirement(&self, depth: usize, parent: bool) -> Format {
+        let format = self.text.clone().set_indent(depth.min(15) as u8);
+        if parent { format.set_bold() } else { format }
+    }
+}
+
+fn write_workbook(project: &ProjectFull, categories: &[&CategoryFull], path: &str) -> std::result::Result<(), XlsxError> {
+    let formats = Formats::new();
+    let mut workbook = Workbook::new();
+
+    write_summary(workbook.add_worksheet().set_name(SUMMARY_SHEET)?, project, categories, &formats)?;
+
+    let mut used_names = HashSet::from([SUMMARY_SHEET.to_lowercase()]);
+    for category in categories {
+        let name = sheet_name(&category.name, &mut used_names);
+        write_category(workbook.add_worksheet().set_name(name)?, category, &formats)?;
+    }
+
+    workbook.save(path)
+}
+
+fn write_summary(
+    sheet: &mut Worksheet,
+    project: &ProjectFull,
+    categories: &[&CategoryFull],
+    formats: &Formats,
+) -> std::result::Result<(), XlsxError> {
+    sheet.write_string_with_format(0, 0, "Matrice de conformitate", &formats.title)?;
+
+    let details = [
+        ("Proiect", Some(project.title.clone())),
+        ("Data", Some(project.date.format("%d.%m.%Y").to_string())),
+        ("Autoritate contractantă", project.metadata.contracting_authority.clone()),
+        ("Număr anunț", project.metadata.notice_number.clone()),
+        ("Stare", Some(status_label(&project.status).to_string())),
+        ("Generat la", Some(Local::now().format("%d.%m.%Y %H:%M").to_string())),
+    ];
+    let mut row = 2;
+    for (label, value) in details {
+        let Some(value) = value else { continue };
+        sheet.write_string_with_format(row, 0, label, &formats.label)?;
+        sheet.write_string(row, 1, value)?;
+        row += 1;
+    }
+
+    row += 1;
+    let header_row = row;
+    for (col, title) in ["Categorie", "Total", "Propuse", "Verificate", "Complete", "Progres"].iter().enumerate() {
+        sheet.write_string_with_format(row, col as u16, *title, &formats.header)?;
+    }
+
+    let mut totals = Counts::default();
+    for category in categories {
+        row += 1;
+        let counts = count(&category.checklist);
+        write_counts(sheet, row, &category.name, &counts, &formats.text, &formats.number, &formats.percent)?;
+        totals.add(&counts);
+    }
+    row += 1;
+    write_counts(sheet, row, "Total", &totals, &formats.total, &formats.total, &formats.total_percent)?;
+
+    sheet.set_column_width(0, 28)?;
+    for col in 1..=5 {
+        sheet.set_column_width(col, 12)?;
+    }
+    sheet.set_freeze_panes(header_row + 1, 0)?;
+    Ok(())
+}
+
+fn write_counts(
+    sheet: &mut Worksheet,
+    row: u32,
+    label: &str,
+    counts: &Counts,
+    label_format: &Format,
+    number: &Format,
+    percent: &Format,
+) -> std::result::Result<(), XlsxError> {
+    sheet.write_string_with_format(row, 0, label, label_format)?;
+    for (col, value) in [counts.total, counts.proposed, counts.verified, counts.complete].into_iter().enumerate() {
+        sheet.write_number_with_format(row, col as u16 + 1, value as f64, number)?;
+    }
+    let progress = if counts.total > 0 { counts.complete as f64 / counts.total as f64 } else { 0.0 };
+    sheet.write_number_with_format(row, 5, progress, percent)?;
+    Ok(())
+}
+
+fn write_category(sheet: &mut Worksheet, category: &CategoryFull, formats: &Formats) -> std::result::Result<(), XlsxError> {
+    for (col, (title, width)) in COLUMNS.iter().enumerate() {
+        sheet.write_string_with_format(0, col as u16, *title, &formats.header)?;
+        sheet.set_column_width(col as u16, *width)?;
+    }
+
+    // (depth, item) in document order
+    let mut stack: Vec<(usize, &ChecklistItemNested)> = category.checklist.iter().rev().map(|i| (0, i)).collect();
+    let mut row = 0;
+    while let Some((depth, item)) = stack.pop() {
+        row += 1;
+        write_item(sheet, row, depth, item, formats)?;
+        stack.extend(item.sub_tasks.iter().rev().map(|sub| (depth + 1, sub)));
+    }
+
+    sheet.set_freeze_panes(1, 2)?;
+    sheet.set_repeat_rows(0, 0)?;
+    sheet.set_landscape();
+    if row > 0 {
+        sheet.autofilter(0, 0, row, COLUMNS.len() as u16 - 1)?;
+    }
+    Ok(())
+}
+
+fn write_item(
+    sheet: &mut Worksheet,
+    row: u32,
+    depth: usize,
+    item: &ChecklistItemNested,
+    formats: &Formats,
+) -> std::result::Result<(), XlsxError> {
+    let text = |value: Option<&str>| cell_text(value.unwrap_or("").to_string());
+    let flag = |value: bool| if value { "Da" } else { "Nu" };
+    let state = if item.proposed && item.verified {
+        "Complet"
+    } else if item.needs_review {
+        "De reverificat"
+    } else {
+        "Incomplet"
+    };
+    let notes = item
+        .notes
+        .iter()
+        .map(|n| format!("{}, {}: {}", n.user, n.date, n.text))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let notes = cell_text(notes);
+
+    sheet.write_string_with_format(row, 0, text(item.reference.as_deref()), &formats.text)?;
+    sheet.write_string_with_format(row, 1, cell_text(item.name.clone()), &formats.requirement(depth, !item.sub_tasks.is_empty()))?;
+    sheet.write_string_with_format(row, 2, text(item.response.as_deref()), &formats.text)?;
+    sheet.write_string_with_format(row, 3, text(item.document_reference.as_deref()), &formats.text)?;
+    sheet.write_string_with_format(row, 4, flag(item.proposed), &formats.text)?;
+    sheet.write_string_with_format(row, 5, text(item.proposed_by.as_deref()), &formats.text)?;
+    write_date(sheet, row, 6, item.proposed_at.filter(|_| item.proposed), formats)?;
+    sheet.write_string_with_format(row, 7, flag(item.verified), &formats.text)?;
+    sheet.write_string_with_format(row, 8, text(item.verified_by.as_deref()), &formats.text)?;
+    write_date(sheet, row, 9, item.verified_at.filter(|_| item.verified), formats)?;
+    sheet.write_string_with_format(row, 10, state, &formats.text)?;
+    sheet.write_string_with_format(row, 11, notes, &formats.text)?;
+    Ok(())
+}
+
+/// Flag times are written as Excel dates in local time; an empty bordered cell if unset.
+fn write_date(
+    sheet: &mut Worksheet,
+    row: u32,
+    col: u16,
+    value: Option<DateTime<Utc>>,
+    formats: &Formats,
+) -> std::result::Result<(), XlsxError> {
+    match value {
+        Some(at) => sheet.write_datetime_with_format(row, col, at.with_timezone(&Local).naive_local(), &formats.date)?,
+        None => sheet.write_blank(row, col, &formats.date)?,
+    };
+    Ok(())
+}
+
+// ─────────────────────────── Helpers ───────────────────────────
+
+#[derive(Default)]
+struct Counts {
+    total: usize,
+    proposed: usize,
+    verified: usize,
+    complete: usize,
+}
+
+impl Counts {
+    fn add(&mut self, other: &Counts) {
+        self.total += other.total;
+        self.proposed += other.proposed;
+        self.verified += other.verified;
+        self.complete += other.complete;
+    }
+}
+
+/// Item counters of a checklist, sub-tasks at every level included.
+fn count(items: &[ChecklistItemNested]) -> Counts {
+    let mut counts = Counts::default();
+    for item in items {
+        counts.total += 1;
+        counts.proposed += item.proposed as usize;
+        counts.verified += item.verified as usize;
+        counts.complete += (item.proposed && item.verified) as usize;
+        counts.add(&count(&item.sub_tasks));
+    }
+    counts
+}
+
+/// `text` cut to what an Excel cell can hold, with a marker when it was cut.
+fn cell_text(text: String) -> String {
+    if text.chars().count() <= MAX_CELL_TEXT {
+        return text;
+    }
+    let keep = MAX_CELL_TEXT - TRUNCATED.chars().count();
+    text.chars().take(keep).chain(TRUNCATED.chars()).collect()
+}
+
+/// A valid, unique sheet name for a category (Excel forbids `[]:*?/\` and more than 31 chars).
+fn sheet_name(category: &str, used: &mut HashSet<String>) -> String {
+    let clean: String = category
+        .chars()
+        .map(|c| if matches!(c, '[' | ']' | ':' | '*' | '?' | '/' | '\\') { '_' } else { c })
+        .collect();
+    let clean = clean.trim().trim_matches('\'');
+    let base: String = if clean.is_empty() { "Categorie" } else { clean }
+        .chars()
+        .take(MAX_SHEET_NAME)
+        .collect();
+
+    let mut name = base.clone();
+    let mut n = 2;
+    while !used.insert(name.to_lowercase()) {
+        let suffix = format!(" ({})", n);
+        let keep = MAX_SHEET_NAME - suffix.chars().count();
+        name = format!("{}{}", base.chars().take(keep).collect::<String>(), suffix);
+        n += 1;
+    }
+    name
+}
+
+fn status_label(status: &str) -> &str {
+    match status {
+        "in_preparation" => "În pregătire",
+        "submitted" => "Depusă",
+        "won" => "Câștigată",
+        "lost" => "Pierdută",
+        "cancelled" => "Anulată",
+        "under_appeal" => "În contestație",
+        other => other,
+    }
+}
@@ -0,0 +1,198 @@
+//! Write guards called at the top of core mutation functions, inside the transaction
+//! of the write they protect. A missing row passes the guard — the caller reports
+//! NotFound as before.
+//!
+//! Archived years block every change. Project and category locks (locks.rs) block
+//! changes to the checklist and notes only; project details and status stay editable.
+//!
+//! A guard holds what it checked until the transaction ends: the year is locked shared
+//! (`archive::archive_year` takes it exclusively before marking the year archived) and
+//! the project and category rows `FOR SHARE` (locking them updates those rows). The
+//! state is read after the locks are taken, so a concurrent archive or lock either
+//! waits for the write or is seen by the guard.
+
+use sqlx::PgConnection;
+
+use crate::errors::{AppError, Result};
+
+fn archived(year: String) -> AppError {
+    AppError::Validation(format!("Anul {} este arhivat și poate fi doar citit", year))
+}
+
+/// Where a checklist change lands: the archived year (if any) and the locks above it.
+struct Target {
+    archived_year: Option<String>,
+    project_locked: bool,
+    category_locked: bool,
+}
+
+fn check(target: Option<Target>) -> Result<()> {
+    let Some(t) = target else { return Ok(()) };
+    if let Some(year) = t.archived_year {
+        return Err(archived(year));
+    }
+    if t.project_locked {
+        return Err(AppError::Validation(
+            "Proiectul este blocat: lista de verificare nu mai poate fi modificată".into(),
+        ));
+    }
+    if t.category_locked {
+        return Err(AppError::Validation(
+            "Categoria este blocată: lista de verificare nu mai poate fi modificată".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Keep the year writable until the transaction ends (shared with other writers).
+async fn lock_year(conn: &mut PgConnection, year: &str) -> Result<()> {
+    sqlx::query!(
+        r#"SELECT 1 AS "locked!" FROM pg_advisory_xact_lock_shared(hashtext('archive:' || $1))"#,
+        year
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(())
+}
+
+/// Wait for the year's pending writes and block new ones until the transaction ends.
+pub(crate) async fn lock_year_exclusive(conn: &mut PgConnection, year: &str) -> Result<()> {
+    sqlx::query!(
+        r#"SELECT 1 AS "locked!" FROM pg_advisory_xact_lock(hashtext('archive:' || $1))"#,
+        year
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(())
+}
+
+pub(crate) async fn ensure_year_writable(conn: &mut PgConnection, year: &str) -> Result<()> {
+    lock_year(conn, year).await?;
+    let archived_year = sqlx::query_scalar!("SELECT year FROM archived_years WHERE year = $1", year)
+        .fetch_optional(conn)
+        .await?;
+    archived_year.map_or(Ok(()), |y| Err(archived(y)))
+}
+
+/// Project details, status, deadlines — not affected by locks.
+pub(crate) async fn ensure_project_writable(conn: &mut PgConnection, project_id: i32) -> Result<()> {
+    let year = sqlx::query_scalar!("SELECT year FROM projects WHERE id = $1", project_id)
+        .fetch_optional(&mut *conn)
+        .await?;
+    match year {
+        Some(year) => ensure_year_writable(conn, &year).await,
+        None => Ok(()),
+    }
+}
+
+/// Adding or reordering the categories of a project.
+pub(crate) async fn ensure_checklist_writable(conn: &mut PgConnection, project_id: i32) -> Result<()> {
+    let year = sqlx::query_scalar!("SELECT year FROM projects WHERE id = $1 FOR SHARE", project_id)
+        .fetch_optional(&mut *conn)
+        .await?;
+    let Some(year) = year else { return Ok(()) };
+    lock_year(conn, &year).await?;
+
+    let target = sqlx::query_as!(
+        Target,
+        r#"SELECT a.year AS "archived_year?", p.locked_at IS NOT NULL AS "project_locked!",
+                  FALSE AS "category_locked!"
+           FROM projects p
+           LEFT JOIN archived_years a ON a.year = p.year
+           WHERE p.id = $1"#,
+        project_id
+    )
+    .fetch_optional(conn)
+    .await?;
+    check(target)
+}
+
+pub(crate) async fn ensure_category_writable(conn: &mut PgConnection, category_id: i32) -> Result<()> {
+    let year = sqlx::query_scalar!(
+        "SELECT p.year FROM categories c
+         JOIN projects p ON p.id = c.project_id
+         WHERE c.id = $1
+         FOR SHARE OF c, p",
+        category_id
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+    let Some(year) = year else { return Ok(()) };
+    lock_year(conn, &year).await?;
+
+    let target = sqlx::query_as!(
+        Target,
+        r#"SELECT a.year AS "archived_year?", p.locked_at IS NOT NULL AS "project_locked!",
+                  c.locked_at IS NOT NULL AS "category_locked!"
+           FROM categories c
+           JOIN projects p ON p.id = c.project_id
+           LEFT JOIN archived_years a ON a.year = p.year
+           WHERE c.id = $1"#,
+        category_id
+    )
+    .fetch_optional(conn)
+    .await?;
+    check(target)
+}
+
+pub(crate) async fn ensure_item_writable(conn: &mut PgConnection, item_id: i32) -> Result<()> {
+    let year = sqlx::query_scalar!(
+        "SELECT p.year FROM checklist_items i
+         JOIN categories c ON c.id = i.category_id
+         JOIN projects p ON p.id = c.project_id
+         WHERE i.id = $1
+         FOR SHARE OF c, p",
+        item_id
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+    let Some(year) = year else { return Ok(()) };
+    lock_year(conn, &year).await?;
+
+    let target = sqlx::query_as!(
+        Target,
+        r#"SELECT a.year AS "archived_year?", p.locked_at IS NOT NULL AS "project_locked!",
+                  c.locked_at IS NOT NULL AS "category_locked!"
+           FROM checklist_items i
+           JOIN categories c ON c.id = i.category_id
+           JOIN projects p ON p.id = c.project_id
+           LEFT JOIN archived_years a ON a.year = p.year
+           WHERE i.id = $1"#,
+        item_id
+    )
+    .fetch_optional(conn)
+    .await?;
+    check(target)
+}
+
+pub(crate) async fn ensure_note_writable(conn: &mut PgConnection, note_id: i32) -> Result<()> {
+    let year = sqlx::query_scalar!(
+        "SELECT p.year FROM notes n
+         JOIN checklist_items i ON i.id = n.item_id
+         JOIN categories c ON c.id = i.category_id
+         JOIN projects p ON p.id = c.project_id
+         WHERE n.id = $1
+         FOR SHARE OF c, p",
+        note_id
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+    let Some(year) = year else { return Ok(()) };
+    lock_year(conn, &year).await?;
+
+    let target = sqlx::query_as!(
+        Target,
+        r#"SELECT a.year AS "archived_year?", p.locked_at IS NOT NULL AS "project_locked!",
+                  c.locked_at IS NOT NULL AS "category_locked!"
+           FROM notes n
+           JOIN checklist_items i ON i.id = n.item_id
+           JOIN categories c ON c.id = i.category_id
+           JOIN projects p ON p.id = c.project_id
+           LEFT JOIN archived_years a ON a.year = p.year
+           WHERE n.id = $1"#,
+        note_id
+    )
+    .fetch_optional(conn)
+    .await?;
+    check(target)
+}
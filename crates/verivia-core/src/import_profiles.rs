@@ -0,0 +1,124 @@
+use sqlx::PgPool;
+
+use crate::errors::{AppError, Result};
+use crate::models::ImportProfile;
+
+// ─────────────────────────── List / Get ───────────────────────────
+
+pub async fn list_import_profiles(pool: &PgPool) -> Result<Vec<ImportProfile>> {
+    let profiles = sqlx::query_as!(
+        ImportProfile,
+        "SELECT id, name, sheet_name, sheet_index, header_row, name_column,
+                description_column, reference_column, response_column,
+                document_reference_column, end_marker
+         FROM import_profiles ORDER BY name"
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(profiles)
+}
+
+pub async fn get_import_profile(pool: &PgPool, id: i32) -> Result<ImportProfile> {
+    sqlx::query_as!(
+        ImportProfile,
+        "SELECT id, name, sheet_name, sheet_index, header_row, name_column,
+                description_column, reference_column, response_column,
+                document_reference_column, end_marker
+         FROM import_profiles WHERE id = $1",
+        id
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Import profile id={} not found", id)))
+}
+
+// ─────────────────────────── Save / Delete ───────────────────────────
+
+/// Create a profile (`id = 0`) or update an existing one. Returns the stored profile.
+pub async fn save_import_profile(pool: &PgPool, profile: &ImportProfile) -> Result<ImportProfile> {
+    let name = profile.name.trim();
+    if name.is_empty() {
+        return Err(AppError::Validation("Numele profilului nu poate fi gol".into()));
+    }
+    let positions = [
+        Some(profile.sheet_index),
+        profile.header_row,
+        Some(profile.name_column),
+        profile.description_column,
+        profile.reference_column,
+        profile.response_column,
+        profile.document_reference_column,
+    ];
+    if positions.into_iter().flatten().any(|n| n < 1) {
+        return Err(AppError::Validation(
+            "Foaia, rândul și coloanele se numerotează de la 1".into(),
+        ));
+    }
+    let sheet_name = profile.sheet_name.as_deref().map(str::trim).filter(|s| !s.is_empty());
+    let end_marker = profile.end_marker.as_deref().map(str::trim).filter(|s| !s.is_empty());
+
+    let saved = if profile.id == 0 {
+        sqlx::query_as!(
+            ImportProfile,
+            "INSERT INTO import_profiles (name, sheet_name, sheet_index, header_row, name_column,
+                 description_column, reference_column, response_column,
+                 document_reference_column, end_marker)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+             ON CONFLICT (name) DO NOTHING
+             RETURNING id, name, sheet_name, sheet_index, header_row, name_column,
+                       description_column, reference_column, response_column,
+                       document_reference_column, end_marker",
+            name, sheet_name, profile.sheet_index, profile.header_row, profile.name_column,
+            profile.description_column, profile.reference_column, profile.response_column,
+            profile.document_reference_column, end_marker
+        )
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| duplicate_name(name))?
+    } else {
+        let name_taken = sqlx::query_scalar!(
+            r#"SELECT EXISTS(SELECT 1 FROM import_profiles WHERE name = $1 AND id <> $2) AS "v!""#,
+            name, profile.id
+        )
+        .fetch_one(pool)
+        .await?;
+        if name_taken {
+            return Err(duplicate_name(name));
+        }
+
+        sqlx::query_as!(
+            ImportProfile,
+            "UPDATE import_profiles SET name = $2, sheet_name = $3, sheet_index = $4, header_row = $5,
+                 name_column = $6, description_column = $7, reference_column = $8, response_column = $9,
+                 document_reference_column = $10, end_marker = $11
+             WHERE id = $1
+             RETURNING id, name, sheet_name, sheet_index, header_row, name_column,
+                       description_column, reference_column, response_column,
+                       document_reference_column, end_marker",
+            profile.id, name, sheet_name, profile.sheet_index, profile.header_row,
+            profile.name_column, profile.description_column, profile.reference_column,
+            profile.response_column, profile.document_reference_column, end_marker
+        )
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Import profile id={} not found", profile.id)))?
+    };
+
+    Ok(saved)
+}
+
+pub async fn delete_import_profile(pool: &PgPool, id: i32) -> Result<()> {
+    let rows = sqlx::query!("DELETE FROM import_profiles WHERE id = $1", id)
+        .execute(pool)
+        .await?
+        .rows_affected();
+
+    if rows == 0 {
+        return Err(AppError::NotFound(format!("Import profile id={} not found", id)));
+    }
+    Ok(())
+}
+
+fn duplicate_name(name: &str) -> AppError {
+    AppError::Validation(format!("Există deja un profil de import numit '{}'", name))
+}
@@ -1,11 +1,28 @@
+pub mod archive;
 pub mod auth;
+pub mod categories;
 pub mod checklist;
 pub mod db;
+pub mod deadlines;
 pub mod errors;
 pub mod excel;
+pub mod export;
+pub mod import_profiles;
+pub mod linked_excel;
+pub mod locks;
 pub mod models;
 pub mod notes;
+pub mod project_query;
 pub mod projects;
+pub mod reimport;
+pub mod search;
+pub mod sheet_import;
+pub mod stats;
+pub mod status;
+pub mod templates;
+pub mod trash;
+mod guard;
+mod tree;
 
 // Re-export PgPool so consumers don't need a direct sqlx dependency
 pub use sqlx::PgPool;
@@ -0,0 +1,135 @@
+//! Upstream changes of the spreadsheets categories were imported from. The content hash
+//! recorded at import is compared with the file on disk; when it differs, the file is
+//! diffed with the checklist exactly like a re-import preview, reading the sheet with the
+//! profile it was imported with.
+
+use std::path::Path;
+
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+
+use crate::errors::{AppError, Result};
+use crate::guard;
+use crate::models::{LinkedExcel, SpecChange};
+use crate::reimport::preview_reimport;
+
+/// SHA-256 of a file's content.
+pub fn file_hash(path: &str) -> Result<String> {
+    let bytes = std::fs::read(path).map_err(|e| AppError::Excel(format!("Cannot read '{}': {}", path, e)))?;
+    Ok(format!("{:x}", Sha256::digest(&bytes)))
+}
+
+// ─────────────────────────── Linked Files ───────────────────────────
+
+/// Categories of the year's projects with an Excel file attached, grouped by file.
+pub async fn linked_files(pool: &PgPool, year: &str) -> Result<Vec<LinkedExcel>> {
+    let linked = sqlx::query_as!(
+        LinkedExcel,
+        r#"SELECT p.id AS project_id, p.title AS project_title, c.id AS category_id,
+                  c.name AS category_name, c.excel_path AS "excel_path!", c.excel_hash,
+                  c.excel_sheet, c.excel_profile_id
+           FROM categories c JOIN projects p ON p.id = c.project_id
+           WHERE p.year = $1 AND p.deleted_at IS NULL AND c.excel_path <> ''
+           ORDER BY c.excel_path, p.title, c.sort_order"#,
+        year
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(linked)
+}
+
+// ─────────────────────────── Check ───────────────────────────
+
+/// Linked files of the year whose requirements changed since they were imported.
+/// Files missing on disk are skipped.
+pub async fn check_year(pool: &PgPool, year: &str) -> Result<Vec<SpecChange>> {
+    let mut changes = Vec::new();
+    for linked in linked_files(pool, year).await? {
+        changes.extend(check_linked(pool, &linked).await?);
+    }
+    Ok(changes)
+}
+
+/// Changes of the categories linked to `path`, e.g. after the file was saved.
+pub async fn check_file(pool: &PgPool, year: &str, path: &str) -> Result<Vec<SpecChange>> {
+    let mut changes = Vec::new();
+    for linked in linked_files(pool, year).await? {
+        if same_path(&linked.excel_path, path) {
+            changes.extend(check_linked(pool, &linked).await?);
+        }
+    }
+    Ok(changes)
+}
+
+/// Take the file's current content as imported without re-importing it: no change is
+/// reported until the file changes again.
+pub async fn acknowledge_change(pool: &PgPool, category_id: i32) -> Result<()> {
+    let path = sqlx::query_scalar!("SELECT excel_path FROM categories WHERE id = $1", category_id)
+        .fetch_optional(pool)
+        .await?
+        .flatten()
+        .filter(|p| !p.is_empty())
+        .ok_or_else(|| AppError::NotFound(format!("Category id={} has no linked Excel file", category_id)))?;
+
+    store_hash(pool, category_id, &file_hash(&path)?).await
+}
+
+/// The change of one linked category, if any. A file whose requirements are the same
+/// (re-saved, formatting only) becomes the new baseline. A file attached but not imported
+/// yet (no hash) has nothing to compare with.
+async fn check_linked(pool: &PgPool, linked: &LinkedExcel) -> Result<Option<SpecChange>> {
+    let Some(imported) = linked.excel_hash.as_deref() else { return Ok(None) };
+    let Ok(hash) = file_hash(&linked.excel_path) else { return Ok(None) };
+    if imported == hash {
+        return Ok(None);
+    }
+
+    let diff = match preview_reimport(
+        pool,
+        linked.category_id,
+        &linked.excel_path,
+        linked.excel_profile_id,
+        linked.excel_sheet.as_deref(),
+    )
+    .await
+    {
+        Ok(diff) => diff,
+        // Not readable right now (still being written, layout lost): retried on the next change
+        Err(AppError::Excel(_)) => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    if diff.has_changes() {
+        return Ok(Some(SpecChange {
+            project_id: linked.project_id,
+            project_title: linked.project_title.clone(),
+            category_name: linked.category_name.clone(),
+            excel_path: linked.excel_path.clone(),
+            diff,
+        }));
+    }
+
+    match store_hash(pool, linked.category_id, &hash).await {
+        // Locked or archived: the baseline stays, the file is compared again next time
+        Err(AppError::Validation(_)) => Ok(None),
+        result => result.map(|_| None),
+    }
+}
+
+async fn store_hash(pool: &PgPool, category_id: i32, hash: &str) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    guard::ensure_category_writable(&mut tx, category_id).await?;
+
+    sqlx::query!("UPDATE categories SET excel_hash = $1 WHERE id = $2", hash, category_id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Whether two paths name the same file (resolved when both exist).
+pub fn same_path(a: &str, b: &str) -> bool {
+    match (Path::new(a).canonicalize(), Path::new(b).canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => Path::new(a) == Path::new(b),
+    }
+}
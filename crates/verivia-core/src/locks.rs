@@ -0,0 +1,228 @@
+use sqlx::{PgPool, Postgres, Transaction};
+
+use crate::auth::require_admin;
+use crate::errors::{AppError, Result};
+use crate::guard;
+use crate::models::{CategoryLock, LockChange, LockState};
+
+// ─────────────────────────── Lock ───────────────────────────
+// A locked project or category keeps its checklist and notes read-only (see guard.rs).
+// Anyone can lock; unlocking needs an admin and a reason. Every change is logged.
+
+pub async fn lock_project(pool: &PgPool, project_id: i32, user: &str, reason: Option<&str>) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    guard::ensure_project_writable(&mut tx, project_id).await?;
+    let locked = sqlx::query!(
+        "UPDATE projects SET locked_at = now(), locked_by = $2
+         WHERE id = $1 AND deleted_at IS NULL AND locked_at IS NULL",
+        project_id, user
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    if locked == 0 {
+        return Err(project_lock_unchanged(&mut tx, project_id, true).await);
+    }
+    record(&mut tx, project_id, None, "lock", user, reason).await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+pub async fn lock_category(pool: &PgPool, category_id: i32, user: &str, reason: Option<&str>) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    guard::ensure_category_writable(&mut tx, category_id).await?;
+    let project_id = sqlx::query_scalar!(
+        "UPDATE categories SET locked_at = now(), locked_by = $2
+         WHERE id = $1 AND locked_at IS NULL
+         RETURNING project_id",
+        category_id, user
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some(project_id) = project_id else {
+        return Err(category_lock_unchanged(&mut tx, category_id, true).await);
+    };
+    record(&mut tx, project_id, Some(category_id), "lock", user, reason).await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Set when a project is marked as submitted (status.rs), so its checklist is frozen
+/// with the offer. An already locked project is left as it is.
+pub(crate) async fn lock_on_submit(
+    tx: &mut Transaction<'_, Postgres>,
+    project_id: i32,
+    user: &str,
+) -> Result<()> {
+    let locked = sqlx::query!(
+        "UPDATE projects SET locked_at = now(), locked_by = $2 WHERE id = $1 AND locked_at IS NULL",
+        project_id, user
+    )
+    .execute(&mut **tx)
+    .await?
+    .rows_affected();
+
+    if locked > 0 {
+        record(tx, project_id, None, "lock", user, Some("Ofertă depusă")).await?;
+    }
+    Ok(())
+}
+
+// ─────────────────────────── Unlock (admins only) ───────────────────────────
+
+pub async fn unlock_project(pool: &PgPool, project_id: i32, user_id: i32, reason: &str) -> Result<()> {
+    let reason = validate_reason(reason)?;
+    // The admin's e-mail is recorded as the author of the unlock
+    let admin = require_admin(pool, user_id).await?;
+
+    let mut tx = pool.begin().await?;
+    guard::ensure_project_writable(&mut tx, project_id).await?;
+    let unlocked = sqlx::query!(
+        "UPDATE projects SET locked_at = NULL, locked_by = NULL
+         WHERE id = $1 AND deleted_at IS NULL AND locked_at IS NOT NULL",
+        project_id
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    if unlocked == 0 {
+        return Err(project_lock_unchanged(&mut tx, project_id, false).await);
+    }
+    record(&mut tx, project_id, None, "unlock", &admin, Some(reason)).await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+pub async fn unlock_category(pool: &PgPool, category_id: i32, user_id: i32, reason: &str) -> Result<()> {
+    let reason = validate_reason(reason)?;
+    let admin = require_admin(pool, user_id).await?;
+
+    let mut tx = pool.begin().await?;
+    let project_id = sqlx::query_scalar!(
+        "UPDATE categories SET locked_at = NULL, locked_by = NULL
+         WHERE id = $1 AND locked_at IS NOT NULL
+         RETURNING project_id",
+        category_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some(project_id) = project_id else {
+        return Err(category_lock_unchanged(&mut tx, category_id, false).await);
+    };
+    // Checked after the update so the project id comes from the same row
+    guard::ensure_project_writable(&mut tx, project_id).await?;
+
+    record(&mut tx, project_id, Some(category_id), "unlock", &admin, Some(reason)).await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+fn validate_reason(reason: &str) -> Result<&str> {
+    let reason = reason.trim();
+    if reason.is_empty() {
+        return Err(AppError::Validation("Motivul deblocării este obligatoriu".into()));
+    }
+    Ok(reason)
+}
+
+// ─────────────────────────── Query ───────────────────────────
+
+pub async fn lock_state(pool: &PgPool, project_id: i32) -> Result<LockState> {
+    let project = sqlx::query!(
+        "SELECT locked_at, locked_by FROM projects WHERE id = $1 AND deleted_at IS NULL",
+        project_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Project id={} not found", project_id)))?;
+
+    let categories = sqlx::query_as!(
+        CategoryLock,
+        "SELECT id AS category_id, name, locked_at, locked_by FROM categories
+         WHERE project_id = $1 ORDER BY sort_order, id",
+        project_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(LockState {
+        project_id,
+        locked_at: project.locked_at,
+        locked_by: project.locked_by,
+        categories,
+    })
+}
+
+/// Lock changes of a project and its categories, oldest first.
+pub async fn lock_history(pool: &PgPool, project_id: i32) -> Result<Vec<LockChange>> {
+    let history = sqlx::query_as!(
+        LockChange,
+        r#"SELECT c.name AS "category?", h.action, h.changed_by, h.changed_at, h.reason
+           FROM lock_history h
+           LEFT JOIN categories c ON c.id = h.category_id
+           WHERE h.project_id = $1
+           ORDER BY h.changed_at, h.id"#,
+        project_id
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(history)
+}
+
+// ─────────────────────────── Helpers ───────────────────────────
+
+async fn record(
+    tx: &mut Transaction<'_, Postgres>,
+    project_id: i32,
+    category_id: Option<i32>,
+    action: &str,
+    user: &str,
+    reason: Option<&str>,
+) -> Result<()> {
+    let reason = reason.map(str::trim).filter(|r| !r.is_empty());
+    sqlx::query!(
+        "INSERT INTO lock_history (project_id, category_id, action, changed_by, reason)
+         VALUES ($1, $2, $3, $4, $5)",
+        project_id, category_id, action, user, reason
+    )
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+/// Why a lock / unlock of a project changed nothing: missing, or already in that state.
+async fn project_lock_unchanged(tx: &mut Transaction<'_, Postgres>, project_id: i32, locking: bool) -> AppError {
+    let exists = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM projects WHERE id = $1 AND deleted_at IS NULL) AS "v!""#,
+        project_id
+    )
+    .fetch_one(&mut **tx)
+    .await;
+
+    match exists {
+        Err(e) => e.into(),
+        Ok(false) => AppError::NotFound(format!("Project id={} not found", project_id)),
+        Ok(true) if locking => AppError::Validation("Proiectul este deja blocat".into()),
+        Ok(true) => AppError::Validation("Proiectul nu este blocat".into()),
+    }
+}
+
+async fn category_lock_unchanged(tx: &mut Transaction<'_, Postgres>, category_id: i32, locking: bool) -> AppError {
+    let exists = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM categories WHERE id = $1) AS "v!""#,
+        category_id
+    )
+    .fetch_one(&mut **tx)
+    .await;
+
+    match exists {
+        Err(e) => e.into(),
+        Ok(false) => AppError::NotFound(format!("Category id={} not found", category_id)),
+        Ok(true) if locking => AppError::Validation("Categoria este deja blocată".into()),
+        Ok(true) => AppError::Validation("Categoria nu este blocată".into()),
+    }
+}
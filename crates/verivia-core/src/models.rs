@@ -1,6 +1,10 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::errors::{AppError, Result};
+
 // ─────────────────────────── Users ───────────────────────────
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
@@ -27,6 +31,9 @@ pub struct UserProfile {
     #[serde(rename = "mail")]
     pub email: String,
     pub roles: HashMap<String, RoleFlags>,
+    /// Admins can unlock locked projects and categories
+    #[serde(rename = "isAdmin", default)]
+    pub is_admin: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -56,9 +63,801 @@ pub struct JwtClaims {
 pub struct Project {
     pub id: i32,
     pub title: String,
-    pub date: String,
+    #[serde(with = "legacy_date")]
+    pub date: NaiveDate,
     pub year: String,
     pub path: Option<String>,
+    #[serde(rename = "submissionDeadline")]
+    pub submission_deadline: Option<DateTime<Utc>>,
+    #[serde(rename = "clarificationDeadline")]
+    pub clarification_deadline: Option<DateTime<Utc>>,
+    #[serde(rename = "openingDate")]
+    pub opening_date: Option<DateTime<Utc>>,
+    #[serde(rename = "contractingAuthority")]
+    pub contracting_authority: Option<String>,
+    #[serde(rename = "noticeNumber")]
+    pub notice_number: Option<String>,
+    #[serde(rename = "cpvCodes")]
+    pub cpv_codes: Vec<String>,
+    #[serde(rename = "estimatedValue")]
+    pub estimated_value: Option<Decimal>,
+    pub currency: Option<String>,
+    #[serde(rename = "procedureType")]
+    pub procedure_type: Option<String>,
+    pub lots: Vec<i32>,
+    pub owner: Option<String>,
+    pub status: String,
+}
+
+impl Project {
+    pub fn metadata(&self) -> ProjectMetadata {
+        ProjectMetadata {
+            contracting_authority: self.contracting_authority.clone(),
+            notice_number: self.notice_number.clone(),
+            cpv_codes: self.cpv_codes.clone(),
+            estimated_value: self.estimated_value,
+            currency: self.currency.clone(),
+            procedure_type: self.procedure_type.clone(),
+            lots: self.lots.clone(),
+            owner: self.owner.clone(),
+        }
+    }
+}
+
+/// Procurement metadata of a project (contracting authority, SEAP/SICAP notice, CPV, value...).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectMetadata {
+    #[serde(default)]
+    pub contracting_authority: Option<String>,
+    /// SEAP/SICAP notice number, e.g. "CN1045678"
+    #[serde(default)]
+    pub notice_number: Option<String>,
+    /// CPV codes with check digit, e.g. "45233120-6"
+    #[serde(default)]
+    pub cpv_codes: Vec<String>,
+    #[serde(default)]
+    pub estimated_value: Option<Decimal>,
+    /// ISO 4217 code, required when `estimated_value` is set
+    #[serde(default)]
+    pub currency: Option<String>,
+    /// One of `projects::PROCEDURE_TYPES`
+    #[serde(default)]
+    pub procedure_type: Option<String>,
+    #[serde(default)]
+    pub lots: Vec<i32>,
+    /// Email of the internal owner of the bid
+    #[serde(default)]
+    pub owner: Option<String>,
+}
+
+/// Optional filters for `list_projects` / `query_projects`. Empty fields do not filter.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectFilter {
+    /// Case-insensitive substring of the contracting authority
+    #[serde(default)]
+    pub contracting_authority: Option<String>,
+    #[serde(default)]
+    pub notice_number: Option<String>,
+    /// Matches projects with any CPV code starting with this prefix (e.g. "4523")
+    #[serde(default)]
+    pub cpv_prefix: Option<String>,
+    #[serde(default)]
+    pub procedure_type: Option<String>,
+    #[serde(default)]
+    pub currency: Option<String>,
+    #[serde(default)]
+    pub min_value: Option<Decimal>,
+    #[serde(default)]
+    pub max_value: Option<Decimal>,
+    #[serde(default)]
+    pub lot: Option<i32>,
+    #[serde(default)]
+    pub owner: Option<String>,
+    /// Any of these lifecycle statuses (empty = all)
+    #[serde(default)]
+    pub statuses: Vec<String>,
+    /// Case-insensitive substring of the title
+    #[serde(default)]
+    pub search: Option<String>,
+    /// Inclusive project date range (MM.DD.YYYY, like the project dates)
+    #[serde(default, with = "legacy_date_opt")]
+    pub date_from: Option<NaiveDate>,
+    #[serde(default, with = "legacy_date_opt")]
+    pub date_to: Option<NaiveDate>,
+    /// "complete" (every item proposed and verified) or "incomplete"
+    #[serde(default)]
+    pub completion: Option<String>,
+}
+
+/// A page request for `query_projects`: filter + keyset cursor.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectQuery {
+    /// Restrict to one year; `None` searches every year
+    #[serde(default)]
+    pub year: Option<String>,
+    #[serde(flatten)]
+    pub filter: ProjectFilter,
+    /// `nextCursor` of the previous page
+    #[serde(default)]
+    pub cursor: Option<String>,
+    /// Page size, defaults to 50 (max 200)
+    #[serde(default)]
+    pub limit: Option<i64>,
+    /// Return per-category progress counters instead of full item trees
+    #[serde(default)]
+    pub summary: bool,
+}
+
+/// One page of projects, newest first. `next_cursor` is absent on the last page.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectPage<T> {
+    pub projects: Vec<T>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// Procurement timeline of a project, as set from the project dialog.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectDeadlines {
+    #[serde(default)]
+    pub submission_deadline: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub clarification_deadline: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub opening_date: Option<DateTime<Utc>>,
+}
+
+/// A project whose submission deadline is near (or past) while items are still unverified.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeadlineAlert {
+    pub project_id: i32,
+    pub title: String,
+    pub year: String,
+    pub submission_deadline: DateTime<Utc>,
+    pub incomplete_categories: Vec<String>,
+    pub unverified_items: i64,
+}
+
+/// A project whose legacy TEXT date could not be converted by migration 010.
+/// Its `date` currently holds `fallback_date`; editing the project clears the issue.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectDateIssue {
+    pub project_id: i32,
+    pub title: String,
+    pub legacy_date: String,
+    pub reason: String,
+    #[serde(with = "legacy_date")]
+    pub fallback_date: NaiveDate,
+}
+
+// ─────────────────────────── Project Dates ───────────────────────────
+// Stored as a real DATE; the frontend still sends and displays MM.DD.YYYY strings.
+
+const LEGACY_DATE_FORMAT: &str = "%m.%d.%Y";
+
+/// Parse a MM.DD.YYYY date as typed in the project dialogs.
+pub fn parse_legacy_date(s: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(s.trim(), LEGACY_DATE_FORMAT).map_err(|_| {
+        AppError::Validation(format!("Data '{}' trebuie să fie în formatul LL.ZZ.AAAA", s.trim()))
+    })
+}
+
+/// Format a date as MM.DD.YYYY (zero-padded), the shape the frontend expects.
+pub fn format_legacy_date(date: NaiveDate) -> String {
+    date.format(LEGACY_DATE_FORMAT).to_string()
+}
+
+/// Build a date from a project folder prefix ("MM.DD") and the year it belongs to.
+pub fn date_from_month_day(month_day: &str, year: &str) -> Result<NaiveDate> {
+    parse_legacy_date(&format!("{}.{}", month_day.trim(), year.trim()))
+}
+
+/// serde adapter: `NaiveDate` ↔ "MM.DD.YYYY"
+pub mod legacy_date {
+    use chrono::NaiveDate;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(date: &NaiveDate, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&super::format_legacy_date(*date))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<NaiveDate, D::Error> {
+        let raw = String::deserialize(d)?;
+        super::parse_legacy_date(&raw).map_err(serde::de::Error::custom)
+    }
+}
+
+/// serde adapter: `Option<NaiveDate>` ↔ "MM.DD.YYYY" (null or blank = none)
+pub mod legacy_date_opt {
+    use chrono::NaiveDate;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(date: &Option<NaiveDate>, s: S) -> Result<S::Ok, S::Error> {
+        match date {
+            Some(date) => super::legacy_date::serialize(date, s),
+            None => s.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Option<NaiveDate>, D::Error> {
+        match Option::<String>::deserialize(d)? {
+            Some(raw) if !raw.trim().is_empty() => {
+                super::parse_legacy_date(&raw).map(Some).map_err(serde::de::Error::custom)
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+/// What to carry over when cloning a project. Structure (categories and the full
+/// item tree, with our responses) is always copied; everything else is reset unless asked for.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CloneOptions {
+    #[serde(default)]
+    pub keep_flags: bool,
+    #[serde(default)]
+    pub keep_notes: bool,
+    /// The linked spreadsheet, with the hash, sheet and profile of its import
+    #[serde(default)]
+    pub keep_excel_path: bool,
+}
+
+// ─────────────────────────── Checklist Import ───────────────────────────
+// Copy one category's item tree from another project (any year) into a target category.
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportMode {
+    /// Add the copied items after the target's existing ones
+    #[default]
+    Append,
+    /// Delete the target's checklist first
+    Replace,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChecklistImportOptions {
+    #[serde(default)]
+    pub mode: ImportMode,
+    #[serde(default)]
+    pub keep_flags: bool,
+    #[serde(default)]
+    pub keep_notes: bool,
+}
+
+/// What an import would copy, shown to the user before confirming.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChecklistImportPreview {
+    pub source_project: String,
+    pub source_year: String,
+    pub source_category: String,
+    pub item_count: i64,
+    pub note_count: i64,
+    /// Items currently in the target category (deleted in Replace mode)
+    pub target_item_count: i64,
+    pub checklist: Vec<ChecklistItemNested>,
+}
+
+// ─────────────────────────── Project Status ───────────────────────────
+
+/// One entry of a project's lifecycle log.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusChange {
+    pub from_status: String,
+    pub to_status: String,
+    pub changed_by: String,
+    pub changed_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub override_reason: Option<String>,
+}
+
+// ─────────────────────────── Locks ───────────────────────────
+
+/// Lock state of a project and each of its categories (`None` = unlocked).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockState {
+    pub project_id: i32,
+    pub locked_at: Option<DateTime<Utc>>,
+    pub locked_by: Option<String>,
+    pub categories: Vec<CategoryLock>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryLock {
+    pub category_id: i32,
+    pub name: String,
+    pub locked_at: Option<DateTime<Utc>>,
+    pub locked_by: Option<String>,
+}
+
+/// One lock / unlock of a project (`category = None`) or one of its categories.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct LockChange {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// "lock" or "unlock"
+    pub action: String,
+    pub changed_by: String,
+    pub changed_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+// ─────────────────────────── Search ───────────────────────────
+
+/// A full-text search match with its project / category / item context.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchHit {
+    /// "project", "item" or "note"
+    pub kind: String,
+    pub project_id: i32,
+    pub project_title: String,
+    pub year: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub item_id: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub item_name: Option<String>,
+    /// Matched text with the search terms wrapped in `<b>…</b>`
+    pub snippet: String,
+    pub rank: f32,
+}
+
+// ─────────────────────────── Statistics ───────────────────────────
+
+/// Item counters (sub-tasks included) with the share of complete items.
+/// An empty checklist counts as 100% complete.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemCounts {
+    pub total: i64,
+    pub proposed: i64,
+    pub verified: i64,
+    pub complete: i64,
+    pub completion_pct: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryStats {
+    pub name: String,
+    #[serde(flatten)]
+    pub counts: ItemCounts,
+}
+
+/// Flags set by one user (from `proposed_by` / `verified_by`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserActivity {
+    pub user: String,
+    pub proposed: i64,
+    pub verified: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectStats {
+    pub project_id: i32,
+    pub title: String,
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub submission_deadline: Option<DateTime<Utc>>,
+    #[serde(flatten)]
+    pub counts: ItemCounts,
+    pub categories: Vec<CategoryStats>,
+    /// Average hours from proposal to verification (items with both timestamps)
+    pub avg_verification_hours: Option<f64>,
+    pub users: Vec<UserActivity>,
+}
+
+/// Dashboard aggregates of one year. `categories` merges same-named categories across projects.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct YearStats {
+    pub year: String,
+    pub project_count: i64,
+    #[serde(flatten)]
+    pub counts: ItemCounts,
+    pub categories: Vec<CategoryStats>,
+    pub avg_verification_hours: Option<f64>,
+    pub users: Vec<UserActivity>,
+    pub projects: Vec<ProjectStats>,
+    /// Projects still in preparation, not complete, with the submission deadline
+    /// passed or within `stats::AT_RISK_DAYS` days
+    pub at_risk: Vec<ProjectStats>,
+}
+
+// ─────────────────────────── Trash ───────────────────────────
+
+/// A soft-deleted project, item (with its sub-tasks) or note.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashEntry {
+    /// "project", "item" or "note"
+    pub kind: String,
+    pub id: i32,
+    /// Project title, item name or note text
+    pub label: String,
+    pub project_id: i32,
+    pub project_title: String,
+    pub year: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    pub deleted_at: DateTime<Utc>,
+}
+
+/// Rows permanently removed by `trash::purge_trash`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PurgeReport {
+    pub projects: u64,
+    pub items: u64,
+    pub notes: u64,
+}
+
+// ─────────────────────────── Year Archives ───────────────────────────
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArchiveMode {
+    /// Keep the year in the database but block every change
+    #[default]
+    ReadOnly,
+    /// Delete the year's projects once the archive file is verified
+    Remove,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedYear {
+    pub year: String,
+    pub archived_at: DateTime<Utc>,
+    pub file_path: String,
+    pub checksum: String,
+    pub removed: bool,
+}
+
+/// Rows written to (or restored from) a year archive file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveCounts {
+    pub projects: usize,
+    pub categories: usize,
+    pub items: usize,
+    pub notes: usize,
+    pub status_changes: usize,
+    pub date_issues: usize,
+    pub lock_changes: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveReport {
+    pub year: String,
+    pub path: String,
+    /// SHA-256 of the archived data, checked again on restore
+    pub checksum: String,
+    pub counts: ArchiveCounts,
+    /// Whether the year's projects are no longer in the database
+    pub removed: bool,
+}
+
+// ─────────────────────────── Excel Import Profiles ───────────────────────────
+
+/// Where the requirements are in a spreadsheet. Rows, columns and the sheet index are
+/// 1-based, as shown in Excel; `header_row = None` detects the header automatically.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportProfile {
+    #[serde(default)]
+    pub id: i32,
+    pub name: String,
+    /// Takes precedence over `sheet_index` when set
+    #[serde(default)]
+    pub sheet_name: Option<String>,
+    #[serde(default = "default_sheet_index")]
+    pub sheet_index: i32,
+    #[serde(default)]
+    pub header_row: Option<i32>,
+    pub name_column: i32,
+    #[serde(default)]
+    pub description_column: Option<i32>,
+    #[serde(default)]
+    pub reference_column: Option<i32>,
+    /// Our response to each requirement, when the document already has one
+    #[serde(default)]
+    pub response_column: Option<i32>,
+    #[serde(default)]
+    pub document_reference_column: Option<i32>,
+    /// Parsing stops at the first row with a cell starting with this text
+    #[serde(default)]
+    pub end_marker: Option<String>,
+}
+
+fn default_sheet_index() -> i32 {
+    1
+}
+
+/// The built-in layout: first sheet, five header rows, requirements in column B.
+impl Default for ImportProfile {
+    fn default() -> Self {
+        ImportProfile {
+            id: 0,
+            name: "Implicit".to_string(),
+            sheet_name: None,
+            sheet_index: 1,
+            header_row: Some(5),
+            name_column: 2,
+            description_column: None,
+            reference_column: None,
+            response_column: None,
+            document_reference_column: None,
+            end_marker: None,
+        }
+    }
+}
+
+/// What the importer found in a spreadsheet, shown to the user before importing.
+/// Positions are 1-based, like `ImportProfile`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LayoutDetection {
+    pub sheet_name: String,
+    pub sheet_index: i32,
+    /// `None` if no row looked like a header; data is then read from the first row
+    pub header_row: Option<i32>,
+    pub name_column: i32,
+    /// Title of the requirement column in the header row
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name_title: Option<String>,
+    pub reference_column: Option<i32>,
+    /// Columns right of the requirements titled as a response / document reference
+    pub response_column: Option<i32>,
+    pub document_reference_column: Option<i32>,
+    /// Rows below the header with text in the requirement column
+    pub data_rows: usize,
+    /// First requirements, as they would be imported
+    pub sample: Vec<String>,
+    /// 0.0 – 1.0: how sure the detection is (header keywords + column content)
+    pub confidence: f64,
+}
+
+impl LayoutDetection {
+    /// Profile that imports exactly what was detected.
+    pub fn profile(&self) -> ImportProfile {
+        ImportProfile {
+            id: 0,
+            name: format!("Detectat: {}", self.sheet_name),
+            sheet_name: Some(self.sheet_name.clone()),
+            sheet_index: self.sheet_index,
+            header_row: self.header_row,
+            name_column: self.name_column,
+            description_column: None,
+            reference_column: self.reference_column,
+            response_column: self.response_column,
+            document_reference_column: self.document_reference_column,
+            end_marker: None,
+        }
+    }
+}
+
+/// A worksheet of a workbook, for choosing what to import.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SheetInfo {
+    pub name: String,
+    /// 1-based, like `ImportProfile::sheet_index`
+    pub index: i32,
+    /// Rows with at least one non-empty cell
+    pub rows: usize,
+}
+
+/// Import one sheet into a category of the project, with a saved profile
+/// or (`profile_id = None`) the detected layout.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SheetMapping {
+    pub sheet_name: String,
+    pub category_name: String,
+    #[serde(default)]
+    pub profile_id: Option<i32>,
+}
+
+/// The checklist a mapped sheet produces (preview) or produced (import).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SheetImport {
+    pub sheet_name: String,
+    pub category_name: String,
+    /// Items including sub-tasks at every level
+    pub item_count: usize,
+    pub checklist: Vec<ChecklistItemNested>,
+    pub report: ImportReport,
+}
+
+/// Requirements parsed from a document, with what the importer could not take as is.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParsedChecklist {
+    pub checklist: Vec<ChecklistItemNested>,
+    pub report: ImportReport,
+}
+
+/// Review of one imported sheet, shown before the checklist is saved.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportReport {
+    pub sheet_name: String,
+    /// Rows below the header up to the end of the data (or the end marker)
+    pub rows_read: usize,
+    /// Rows imported as requirements
+    pub rows_imported: usize,
+    /// Rows without any value, ignored
+    pub empty_rows: usize,
+    pub issues: Vec<ImportIssue>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IssueKind {
+    /// A row with content that produced no requirement
+    SkippedRow,
+    /// A number or date taken as text
+    ConvertedCell,
+    /// A requirement cell covered by a merged region
+    MergedCell,
+    /// Same text as an earlier requirement
+    DuplicateName,
+    /// A cell long enough to hold several requirements
+    LongCell,
+}
+
+/// One finding of an `ImportReport`. Positions are 1-based, like `ImportProfile`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportIssue {
+    pub kind: IssueKind,
+    pub row: i32,
+    pub column: Option<i32>,
+    pub message: String,
+}
+
+// ─────────────────────────── Excel Re-import ───────────────────────────
+// Compare an updated specification with the category's checklist before applying it.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    /// New requirement, inserted without progress
+    Added,
+    /// No longer in the specification; moved to the trash
+    Removed,
+    /// Matched with a changed text; flags and notes are kept, verification is cleared
+    Renamed,
+    /// Matched with the same text; only its position and reference may change
+    Unchanged,
+}
+
+/// One requirement of a re-import, in document order (removed items last).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemChange {
+    pub kind: ChangeKind,
+    /// The existing item; `None` for added requirements
+    pub item_id: Option<i32>,
+    /// Nesting level in the new checklist (in the current one for removed items)
+    pub depth: usize,
+    pub reference: Option<String>,
+    pub name: String,
+    /// Current reference and text of a matched item, when they differ
+    pub previous_reference: Option<String>,
+    pub previous_name: Option<String>,
+    /// Word similarity between the old and new text (1 = same text)
+    pub similarity: f64,
+    /// Progress of the existing item, kept unless it is removed
+    pub proposed: bool,
+    pub verified: bool,
+    pub notes: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChecklistDiff {
+    pub added: usize,
+    pub removed: usize,
+    pub renamed: usize,
+    pub unchanged: usize,
+    pub changes: Vec<ItemChange>,
+}
+
+impl ChecklistDiff {
+    /// Whether applying the re-import would change any requirement or its numbering.
+    pub fn has_changes(&self) -> bool {
+        self.added + self.removed + self.renamed > 0
+            || self.changes.iter().any(|c| c.previous_reference.is_some())
+    }
+}
+
+// ─────────────────────────── Linked Excel Files ───────────────────────────
+// The specification a category was imported from, watched for upstream changes.
+
+/// A category with an Excel file attached (`categories.excel_path`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkedExcel {
+    pub project_id: i32,
+    pub project_title: String,
+    pub category_id: i32,
+    pub category_name: String,
+    pub excel_path: String,
+    /// Content hash at the last import; `None` until the checklist read from it is saved
+    pub excel_hash: Option<String>,
+    /// Sheet and import profile of the last import; `None` for the detected ones
+    pub excel_sheet: Option<String>,
+    pub excel_profile_id: Option<i32>,
+}
+
+/// A linked specification whose requirements changed since the last import.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpecChange {
+    pub project_id: i32,
+    pub project_title: String,
+    pub category_name: String,
+    pub excel_path: String,
+    /// What re-importing the file would do (see `reimport::apply_reimport`)
+    pub diff: ChecklistDiff,
+}
+
+// ─────────────────────────── Checklist Export ───────────────────────────
+
+/// An xlsx compliance matrix written by `export::export_checklist`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChecklistExport {
+    pub path: String,
+    /// Category sheets plus the summary sheet
+    pub sheets: usize,
+    /// Items including sub-tasks at every level
+    pub items: usize,
+}
+
+// ─────────────────────────── Project Templates ───────────────────────────
+// A template defines the categories and checklist items a new project starts with.
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectTemplate {
+    pub id: i32,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub categories: Vec<TemplateCategory>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateCategory {
+    pub name: String,
+    pub items: Vec<TemplateItem>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateItem {
+    pub name: String,
+    #[serde(default, rename = "subTasks")]
+    pub sub_tasks: Vec<TemplateItem>,
 }
 
 // ─────────────────────────── Categories ───────────────────────────
@@ -69,6 +868,7 @@ pub struct Category {
     pub project_id: i32,
     pub name: String,
     pub excel_path: Option<String>,
+    pub sort_order: i32,
 }
 
 // ─────────────────────────── Checklist Items ───────────────────────────
@@ -84,6 +884,13 @@ pub struct ChecklistItem {
     pub sort_order: i32,
     pub proposed_by: Option<String>,
     pub verified_by: Option<String>,
+    pub proposed_at: Option<DateTime<Utc>>,
+    pub verified_at: Option<DateTime<Utc>>,
+    pub reference: Option<String>,
+    pub needs_review: bool,
+    pub previous_name: Option<String>,
+    pub response: Option<String>,
+    pub document_reference: Option<String>,
 }
 
 // ─────────────────────────── Notes ───────────────────────────
@@ -105,9 +912,20 @@ pub struct Note {
 pub struct ProjectFull {
     pub id: i32,
     pub title: String,
-    pub date: String,
+    #[serde(with = "legacy_date")]
+    pub date: NaiveDate,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub path: Option<String>,
+    #[serde(default, rename = "submissionDeadline", skip_serializing_if = "Option::is_none")]
+    pub submission_deadline: Option<DateTime<Utc>>,
+    #[serde(default, rename = "clarificationDeadline", skip_serializing_if = "Option::is_none")]
+    pub clarification_deadline: Option<DateTime<Utc>>,
+    #[serde(default, rename = "openingDate", skip_serializing_if = "Option::is_none")]
+    pub opening_date: Option<DateTime<Utc>>,
+    #[serde(flatten)]
+    pub metadata: ProjectMetadata,
+    #[serde(default = "default_status")]
+    pub status: String,
     pub categories: Vec<CategoryFull>,
 }
 
@@ -119,6 +937,40 @@ pub struct CategoryFull {
     pub checklist: Vec<ChecklistItemNested>,
 }
 
+/// Project without item trees — only per-category progress counters.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectSummary {
+    pub id: i32,
+    pub title: String,
+    #[serde(with = "legacy_date")]
+    pub date: NaiveDate,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(default, rename = "submissionDeadline", skip_serializing_if = "Option::is_none")]
+    pub submission_deadline: Option<DateTime<Utc>>,
+    #[serde(default, rename = "clarificationDeadline", skip_serializing_if = "Option::is_none")]
+    pub clarification_deadline: Option<DateTime<Utc>>,
+    #[serde(default, rename = "openingDate", skip_serializing_if = "Option::is_none")]
+    pub opening_date: Option<DateTime<Utc>>,
+    #[serde(flatten)]
+    pub metadata: ProjectMetadata,
+    pub status: String,
+    pub categories: Vec<CategoryProgress>,
+}
+
+/// Item counters of one category (sub-tasks included).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryProgress {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub excel_path: Option<String>,
+    pub total: i64,
+    pub proposed: i64,
+    pub verified: i64,
+    pub complete: i64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChecklistItemNested {
     #[serde(default)]
@@ -138,16 +990,43 @@ pub struct ChecklistItemNested {
     pub proposed_by: Option<String>,
     #[serde(default, rename = "verifiedBy", skip_serializing_if = "Option::is_none")]
     pub verified_by: Option<String>,
+    /// Sent back unchanged on save so bulk saves keep the original flag times
+    #[serde(default, rename = "proposedAt", skip_serializing_if = "Option::is_none")]
+    pub proposed_at: Option<DateTime<Utc>>,
+    #[serde(default, rename = "verifiedAt", skip_serializing_if = "Option::is_none")]
+    pub verified_at: Option<DateTime<Utc>>,
+    /// Numbering from the imported document ("1.2", "b)"), shown before the name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+    /// Text changed by a re-import: verification was cleared, cleared again when re-verified
+    #[serde(default, rename = "needsReview", skip_serializing_if = "std::ops::Not::not")]
+    pub needs_review: bool,
+    /// The requirement text before the re-import that changed it
+    #[serde(default, rename = "previousName", skip_serializing_if = "Option::is_none")]
+    pub previous_name: Option<String>,
+    /// Our offer's answer to the requirement ("Conform – vezi pag. 34 din PT")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response: Option<String>,
+    /// Where the answer is documented (document, page, annex)
+    #[serde(default, rename = "documentReference", skip_serializing_if = "Option::is_none")]
+    pub document_reference: Option<String>,
 }
 
-/// Note as the frontend sees it — just user, date, text
+/// Note as the frontend sees it — user, date, text and the id to delete it by
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NoteNested {
+    /// 0 for a note not saved yet
+    #[serde(default)]
+    pub id: i32,
     pub user: String,
     pub date: String,
     pub text: String,
 }
 
+fn default_status() -> String {
+    "in_preparation".to_string()
+}
+
 fn default_incomplete() -> String {
     "incomplete".to_string()
 }
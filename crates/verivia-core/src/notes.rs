@@ -1,6 +1,7 @@
 use sqlx::PgPool;
 
 use crate::errors::{AppError, Result};
+use crate::guard;
 use crate::models::Note;
 
 pub async fn add_note(
@@ -10,6 +11,9 @@ pub async fn add_note(
     date: &str,
     text: &str,
 ) -> Result<Note> {
+    let mut tx = pool.begin().await?;
+    guard::ensure_item_writable(&mut tx, item_id).await?;
+
     let note = sqlx::query_as!(
         Note,
         r#"INSERT INTO notes (item_id, "user", date, text)
@@ -20,19 +24,23 @@ pub async fn add_note(
         date,
         text
     )
-    .fetch_one(pool)
+    .fetch_one(&mut *tx)
     .await?;
 
+    tx.commit().await?;
     Ok(note)
 }
 
 pub async fn edit_note(pool: &PgPool, note_id: i32, new_text: &str) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    guard::ensure_note_writable(&mut tx, note_id).await?;
+
     let rows = sqlx::query!(
-        "UPDATE notes SET text = $1 WHERE id = $2",
+        "UPDATE notes SET text = $1 WHERE id = $2 AND deleted_at IS NULL",
         new_text,
         note_id
     )
-    .execute(pool)
+    .execute(&mut *tx)
     .await?
     .rows_affected();
 
@@ -40,12 +48,18 @@ pub async fn edit_note(pool: &PgPool, note_id: i32, new_text: &str) -> Result<()
         return Err(AppError::NotFound(format!("Note id={} not found", note_id)));
     }
 
+    tx.commit().await?;
     Ok(())
 }
 
+/// Move a note to the trash (see `trash::restore_note`).
 pub async fn delete_note(pool: &PgPool, note_id: i32) -> Result<()> {
-    sqlx::query!("DELETE FROM notes WHERE id = $1", note_id)
-        .execute(pool)
+    let mut tx = pool.begin().await?;
+    guard::ensure_note_writable(&mut tx, note_id).await?;
+
+    sqlx::query!("UPDATE notes SET deleted_at = now() WHERE id = $1 AND deleted_at IS NULL", note_id)
+        .execute(&mut *tx)
         .await?;
+    tx.commit().await?;
     Ok(())
 }
@@ -0,0 +1,219 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use sqlx::PgPool;
+
+use crate::errors::{AppError, Result};
+use crate::models::{
+    CategoryProgress, Project, ProjectFilter, ProjectFull, ProjectPage, ProjectQuery,
+    ProjectSummary,
+};
+use crate::projects::load_project_trees;
+
+const DEFAULT_PAGE_SIZE: i64 = 50;
+const MAX_PAGE_SIZE: i64 = 200;
+
+// ─────────────────────────── Paged Queries ───────────────────────────
+
+/// One page of projects matching `query`, with full checklists and notes.
+pub async fn query_projects(pool: &PgPool, query: &ProjectQuery) -> Result<ProjectPage<ProjectFull>> {
+    let (projects, next_cursor) = find_page(pool, query).await?;
+    Ok(ProjectPage {
+        projects: load_project_trees(pool, &projects).await?,
+        next_cursor,
+    })
+}
+
+/// One page of projects matching `query`, with per-category progress counters only.
+pub async fn query_project_summaries(
+    pool: &PgPool,
+    query: &ProjectQuery,
+) -> Result<ProjectPage<ProjectSummary>> {
+    let (projects, next_cursor) = find_page(pool, query).await?;
+    Ok(ProjectPage {
+        projects: summarize_projects(pool, &projects).await?,
+        next_cursor,
+    })
+}
+
+/// Lightweight list of a year's projects matching `filter`: per-category counters,
+/// no checklists. Open a category with `checklist::load_category`.
+pub async fn list_project_summaries(
+    pool: &PgPool,
+    year: &str,
+    filter: &ProjectFilter,
+) -> Result<Vec<ProjectSummary>> {
+    let projects = find_projects(pool, Some(year), filter, None, None).await?;
+    summarize_projects(pool, &projects).await
+}
+
+/// Attach category counters to `projects` (1 query), keeping their order.
+async fn summarize_projects(
+    pool: &PgPool,
+    projects: &[Project],
+) -> Result<Vec<ProjectSummary>> {
+    let project_ids: Vec<i32> = projects.iter().map(|p| p.id).collect();
+
+    let counts = sqlx::query!(
+        r#"SELECT c.project_id, c.name, c.excel_path,
+                  COUNT(i.id) AS "total!",
+                  COUNT(i.id) FILTER (WHERE i.proposed) AS "proposed!",
+                  COUNT(i.id) FILTER (WHERE i.verified) AS "verified!",
+                  COUNT(i.id) FILTER (WHERE i.proposed AND i.verified) AS "complete!"
+           FROM categories c
+           LEFT JOIN checklist_items i ON i.category_id = c.id AND i.deleted_at IS NULL
+           WHERE c.project_id = ANY($1)
+           GROUP BY c.id
+           ORDER BY c.sort_order, c.id"#,
+        &project_ids
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut cats_by_project: HashMap<i32, Vec<CategoryProgress>> = HashMap::new();
+    for c in counts {
+        cats_by_project.entry(c.project_id).or_default().push(CategoryProgress {
+            name: c.name,
+            excel_path: c.excel_path,
+            total: c.total,
+            proposed: c.proposed,
+            verified: c.verified,
+            complete: c.complete,
+        });
+    }
+
+    let result = projects
+        .iter()
+        .map(|project| ProjectSummary {
+            id: project.id,
+            title: project.title.clone(),
+            date: project.date,
+            path: project.path.clone(),
+            submission_deadline: project.submission_deadline,
+            clarification_deadline: project.clarification_deadline,
+            opening_date: project.opening_date,
+            metadata: project.metadata(),
+            status: project.status.clone(),
+            categories: cats_by_project.remove(&project.id).unwrap_or_default(),
+        })
+        .collect();
+
+    Ok(result)
+}
+
+async fn find_page(pool: &PgPool, query: &ProjectQuery) -> Result<(Vec<Project>, Option<String>)> {
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE);
+    if !(1..=MAX_PAGE_SIZE).contains(&limit) {
+        return Err(AppError::Validation(format!(
+            "Dimensiunea paginii trebuie să fie între 1 și {}",
+            MAX_PAGE_SIZE
+        )));
+    }
+    let after = query.cursor.as_deref().map(parse_cursor).transpose()?;
+
+    // One extra row tells whether another page follows
+    let mut projects =
+        find_projects(pool, query.year.as_deref(), &query.filter, after, Some(limit + 1)).await?;
+
+    let next_cursor = if projects.len() as i64 > limit {
+        projects.truncate(limit as usize);
+        projects.last().map(|p| format!("{}_{}", p.date.format("%Y-%m-%d"), p.id))
+    } else {
+        None
+    };
+
+    Ok((projects, next_cursor))
+}
+
+/// Trimmed filter text with the LIKE wildcards (`%`, `_`) and the escape character
+/// escaped, so it matches literally inside `'%' || $n || '%'`.
+fn like_pattern(value: &Option<String>) -> Option<String> {
+    let value = value.as_deref().map(str::trim).filter(|s| !s.is_empty())?;
+    Some(value.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_"))
+}
+
+/// Cursor = "YYYY-MM-DD_id" of the last project on the previous page.
+fn parse_cursor(cursor: &str) -> Result<(NaiveDate, i32)> {
+    cursor
+        .split_once('_')
+        .and_then(|(date, id)| {
+            Some((NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?, id.parse().ok()?))
+        })
+        .ok_or_else(|| AppError::Validation(format!("Cursor invalid: '{}'", cursor)))
+}
+
+// ─────────────────────────── Filtered Project Rows ───────────────────────────
+
+/// Projects matching `filter`, newest first (`date DESC, id DESC`).
+/// `year = None` searches every year; `after` continues past a cursor position;
+/// `limit = None` returns every match.
+pub(crate) async fn find_projects(
+    pool: &PgPool,
+    year: Option<&str>,
+    filter: &ProjectFilter,
+    after: Option<(NaiveDate, i32)>,
+    limit: Option<i64>,
+) -> Result<Vec<Project>> {
+    let completion = filter.completion.as_deref().map(str::trim).filter(|s| !s.is_empty());
+    if let Some(c) = completion {
+        if c != "complete" && c != "incomplete" {
+            return Err(AppError::Validation(format!(
+                "Stare de completare necunoscută: '{}'",
+                c
+            )));
+        }
+    }
+
+    let projects = sqlx::query_as!(
+        Project,
+        "SELECT id, title, date, year, path, submission_deadline, clarification_deadline, opening_date,
+                contracting_authority, notice_number, cpv_codes, estimated_value, currency,
+                procedure_type, lots, owner, status
+         FROM projects
+         WHERE deleted_at IS NULL
+           AND ($1::text IS NULL OR year = $1)
+           AND ($2::text IS NULL OR contracting_authority ILIKE '%' || $2 || '%')
+           AND ($3::text IS NULL OR notice_number = $3)
+           AND ($4::text IS NULL OR EXISTS (SELECT 1 FROM unnest(cpv_codes) cpv WHERE cpv LIKE $4 || '%'))
+           AND ($5::text IS NULL OR procedure_type = $5)
+           AND ($6::text IS NULL OR currency = $6)
+           AND ($7::numeric IS NULL OR estimated_value >= $7)
+           AND ($8::numeric IS NULL OR estimated_value <= $8)
+           AND ($9::int IS NULL OR $9 = ANY(lots))
+           AND ($10::text IS NULL OR owner = $10)
+           AND (cardinality($11::text[]) = 0 OR status = ANY($11))
+           AND ($12::text IS NULL OR title ILIKE '%' || $12 || '%')
+           AND ($13::date IS NULL OR date >= $13)
+           AND ($14::date IS NULL OR date <= $14)
+           AND ($15::text IS NULL OR ($15 = 'complete') = NOT EXISTS (
+                   SELECT 1 FROM categories c
+                   JOIN checklist_items i ON i.category_id = c.id
+                   WHERE c.project_id = projects.id AND i.deleted_at IS NULL
+                     AND NOT (i.proposed AND i.verified)))
+           AND ($16::date IS NULL OR (date, id) < ($16, $17::int))
+         ORDER BY date DESC, id DESC
+         LIMIT $18",
+        year,
+        like_pattern(&filter.contracting_authority),
+        filter.notice_number.as_deref().map(|s| s.trim().to_uppercase()).filter(|s| !s.is_empty()),
+        like_pattern(&filter.cpv_prefix),
+        filter.procedure_type.as_deref().map(str::trim).filter(|s| !s.is_empty()),
+        filter.currency.as_deref().map(|s| s.trim().to_uppercase()).filter(|s| !s.is_empty()),
+        filter.min_value,
+        filter.max_value,
+        filter.lot,
+        filter.owner.as_deref().map(|s| s.trim().to_lowercase()).filter(|s| !s.is_empty()),
+        &filter.statuses,
+        like_pattern(&filter.search),
+        filter.date_from,
+        filter.date_to,
+        completion,
+        after.map(|(date, _)| date),
+        after.map(|(_, id)| id),
+        limit,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(projects)
+}
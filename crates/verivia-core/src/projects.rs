@@ -1,11 +1,18 @@
 use std::collections::HashMap;
 
+use chrono::NaiveDate;
+use lazy_static::lazy_static;
+use regex::Regex;
 use sqlx::PgPool;
 
 use crate::errors::{AppError, Result};
+use crate::guard;
 use crate::models::{
-    Category, CategoryFull, ChecklistItem, ChecklistItemNested, NoteNested, Project, ProjectFull,
+    Category, CategoryFull, ChecklistItem, ChecklistItemNested, CloneOptions, NoteNested, Project,
+    ProjectDateIssue, ProjectFilter, ProjectFull, ProjectMetadata,
 };
+use crate::project_query::find_projects;
+use crate::tree::copy_checklist_items;
 
 /// Default categories created for every new project
 const DEFAULT_CATEGORIES: &[&str] = &["Eligibilitate", "Financiar", "Tehnic", "PTE/PCCVI"];
@@ -22,18 +29,46 @@ const ELIGIBILITATE_DEFAULTS: &[&str] = &[
 const FINANCIAR_DEFAULTS: &[&str] = &["Propunere financiara"];
 const PTE_DEFAULTS: &[&str] = &["PTE/PCCVI"];
 
+/// Award procedures (Legea 98/2016), stored in `projects.procedure_type`
+pub const PROCEDURE_TYPES: &[&str] = &[
+    "licitatie_deschisa",
+    "licitatie_restransa",
+    "negociere_competitiva",
+    "dialog_competitiv",
+    "parteneriat_inovare",
+    "negociere_fara_publicare",
+    "concurs_solutii",
+    "servicii_sociale",
+    "procedura_simplificata",
+    "achizitie_directa",
+];
+
+lazy_static! {
+    /// SEAP/SICAP notice numbers: CN1045678, SCN1098765, ADV1234567, ...
+    static ref RE_NOTICE: Regex = Regex::new(r"^[A-Z]{2,4}\d{5,10}$").unwrap();
+    /// CPV code with check digit: 45233120-6
+    static ref RE_CPV: Regex = Regex::new(r"^\d{8}-\d$").unwrap();
+    static ref RE_CURRENCY: Regex = Regex::new(r"^[A-Z]{3}$").unwrap();
+}
+
 // ─────────────────────────── List Projects (batch — 4 queries total) ───────────────────────────
 
-pub async fn list_projects(pool: &PgPool, year: &str) -> Result<Vec<ProjectFull>> {
-    // 1) All projects for this year
-    let projects = sqlx::query_as!(
-        Project,
-        "SELECT id, title, date, year, path FROM projects WHERE year = $1 ORDER BY date DESC",
-        year
-    )
-    .fetch_all(pool)
-    .await?;
+pub async fn list_projects(
+    pool: &PgPool,
+    year: &str,
+    filter: &ProjectFilter,
+) -> Result<Vec<ProjectFull>> {
+    // 1) All projects for this year matching the filter
+    let projects = find_projects(pool, Some(year), filter, None, None).await?;
+    load_project_trees(pool, &projects).await
+}
 
+/// Queries 2–4 of `list_projects`: categories, items and notes of `projects`,
+/// assembled into the nested frontend shape (project order is kept).
+pub(crate) async fn load_project_trees(
+    pool: &PgPool,
+    projects: &[Project],
+) -> Result<Vec<ProjectFull>> {
     if projects.is_empty() {
         return Ok(vec![]);
     }
@@ -43,7 +78,8 @@ pub async fn list_projects(pool: &PgPool, year: &str) -> Result<Vec<ProjectFull>
     // 2) All categories for those projects (1 query)
     let all_categories = sqlx::query_as!(
         Category,
-        "SELECT id, project_id, name, excel_path FROM categories WHERE project_id = ANY($1) ORDER BY id",
+        "SELECT id, project_id, name, excel_path, sort_order FROM categories
+         WHERE project_id = ANY($1) ORDER BY sort_order, id",
         &project_ids
     )
     .fetch_all(pool)
@@ -54,8 +90,10 @@ pub async fn list_projects(pool: &PgPool, year: &str) -> Result<Vec<ProjectFull>
     // 3) All checklist items for those categories (1 query)
     let all_items = sqlx::query_as!(
         ChecklistItem,
-        "SELECT id, category_id, parent_id, name, proposed, verified, sort_order, proposed_by, verified_by
-         FROM checklist_items WHERE category_id = ANY($1) ORDER BY sort_order, id",
+        "SELECT id, category_id, parent_id, name, proposed, verified, sort_order, proposed_by, verified_by,
+                proposed_at, verified_at, reference, needs_review, previous_name, response, document_reference
+         FROM checklist_items WHERE category_id = ANY($1) AND deleted_at IS NULL
+         ORDER BY sort_order, id",
         &category_ids
     )
     .fetch_all(pool)
@@ -65,7 +103,8 @@ pub async fn list_projects(pool: &PgPool, year: &str) -> Result<Vec<ProjectFull>
 
     // 4) All notes for those items (1 query)
     let all_notes = sqlx::query!(
-        r#"SELECT item_id, "user", date, text FROM notes WHERE item_id = ANY($1) ORDER BY id"#,
+        r#"SELECT id, item_id, "user", date, text FROM notes
+           WHERE item_id = ANY($1) AND deleted_at IS NULL ORDER BY id"#,
         &item_ids
     )
     .fetch_all(pool)
@@ -80,6 +119,7 @@ pub async fn list_projects(pool: &PgPool, year: &str) -> Result<Vec<ProjectFull>
             .entry(n.item_id)
             .or_default()
             .push(NoteNested {
+                id: n.id,
                 user: n.user,
                 date: n.date,
                 text: n.text,
@@ -135,8 +175,13 @@ pub async fn list_projects(pool: &PgPool, year: &str) -> Result<Vec<ProjectFull>
             ProjectFull {
                 id: project.id,
                 title: project.title.clone(),
-                date: project.date.clone(),
+                date: project.date,
                 path: project.path.clone(),
+                submission_deadline: project.submission_deadline,
+                clarification_deadline: project.clarification_deadline,
+                opening_date: project.opening_date,
+                metadata: project.metadata(),
+                status: project.status.clone(),
                 categories,
             }
         })
@@ -146,7 +191,7 @@ pub async fn list_projects(pool: &PgPool, year: &str) -> Result<Vec<ProjectFull>
 }
 
 /// Recursively build nested checklist items from pre-fetched data (no DB calls)
-fn build_nested(
+pub(crate) fn build_nested(
     item: &ChecklistItem,
     children_by_parent: &HashMap<i32, Vec<&ChecklistItem>>,
     notes_by_item: &HashMap<i32, Vec<NoteNested>>,
@@ -178,28 +223,52 @@ fn build_nested(
         sub_tasks,
         proposed_by: item.proposed_by.clone(),
         verified_by: item.verified_by.clone(),
+        proposed_at: item.proposed_at,
+        verified_at: item.verified_at,
+        reference: item.reference.clone(),
+        needs_review: item.needs_review,
+        previous_name: item.previous_name.clone(),
+        response: item.response.clone(),
+        document_reference: item.document_reference.clone(),
     }
 }
 
 // ─────────────────────────── Add Project ───────────────────────────
 
-pub async fn add_project(pool: &PgPool, title: &str, date: &str, year: &str) -> Result<Project> {
+/// Create a project with its categories and starting checklist.
+/// `template_id` selects a named project template; `None` uses the built-in defaults.
+pub async fn add_project(
+    pool: &PgPool,
+    title: &str,
+    date: NaiveDate,
+    year: &str,
+    template_id: Option<i32>,
+) -> Result<Project> {
     let mut tx = pool.begin().await?;
+    guard::ensure_year_writable(&mut tx, year).await?;
 
     let project = sqlx::query_as!(
         Project,
         "INSERT INTO projects (title, date, year)
          VALUES ($1, $2, $3)
-         RETURNING id, title, date, year, path",
+         RETURNING id, title, date, year, path, submission_deadline, clarification_deadline, opening_date,
+                contracting_authority, notice_number, cpv_codes, estimated_value, currency,
+                procedure_type, lots, owner, status",
         title, date, year
     )
     .fetch_one(&mut *tx)
     .await?;
 
-    for cat_name in DEFAULT_CATEGORIES {
+    if let Some(tid) = template_id {
+        crate::templates::apply_template(&mut tx, tid, project.id).await?;
+        tx.commit().await?;
+        return Ok(project);
+    }
+
+    for (order, cat_name) in DEFAULT_CATEGORIES.iter().enumerate() {
         let cat_id = sqlx::query_scalar!(
-            "INSERT INTO categories (project_id, name) VALUES ($1, $2) RETURNING id",
-            project.id, *cat_name
+            "INSERT INTO categories (project_id, name, sort_order) VALUES ($1, $2, $3) RETURNING id",
+            project.id, *cat_name, order as i32
         )
         .fetch_one(&mut *tx)
         .await?;
@@ -227,21 +296,245 @@ pub async fn add_project(pool: &PgPool, title: &str, date: &str, year: &str) ->
 
 // ─────────────────────────── Edit Project ───────────────────────────
 
-pub async fn edit_project(pool: &PgPool, id: i32, new_title: &str, new_date: &str) -> Result<()> {
+pub async fn edit_project(pool: &PgPool, id: i32, new_title: &str, new_date: NaiveDate) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    guard::ensure_project_writable(&mut tx, id).await?;
+
     let rows = sqlx::query!(
-        "UPDATE projects SET title = $1, date = $2 WHERE id = $3",
+        "UPDATE projects SET title = $1, date = $2 WHERE id = $3 AND deleted_at IS NULL",
         new_title, new_date, id
     )
-    .execute(pool)
+    .execute(&mut *tx)
     .await?
     .rows_affected();
 
     if rows == 0 {
         return Err(AppError::NotFound(format!("Project id={} not found", id)));
     }
+
+    // A date entered by hand supersedes the fallback chosen by the DATE migration
+    sqlx::query!("DELETE FROM project_date_issues WHERE project_id = $1", id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
     Ok(())
 }
 
+/// Projects whose legacy date string could not be converted to a DATE.
+pub async fn list_date_issues(pool: &PgPool) -> Result<Vec<ProjectDateIssue>> {
+    let issues = sqlx::query_as!(
+        ProjectDateIssue,
+        "SELECT i.project_id, p.title, i.legacy_date, i.reason, i.fallback_date
+         FROM project_date_issues i
+         JOIN projects p ON p.id = i.project_id
+         WHERE p.deleted_at IS NULL
+         ORDER BY p.year, i.project_id"
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(issues)
+}
+
+// ─────────────────────────── Procurement Metadata ───────────────────────────
+
+pub async fn set_project_metadata(
+    pool: &PgPool,
+    project_id: i32,
+    metadata: &ProjectMetadata,
+) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    guard::ensure_project_writable(&mut tx, project_id).await?;
+
+    let m = validate_metadata(metadata)?;
+
+    let rows = sqlx::query!(
+        "UPDATE projects
+         SET contracting_authority = $1, notice_number = $2, cpv_codes = $3, estimated_value = $4,
+             currency = $5, procedure_type = $6, lots = $7, owner = $8
+         WHERE id = $9 AND deleted_at IS NULL",
+        m.contracting_authority,
+        m.notice_number,
+        &m.cpv_codes,
+        m.estimated_value,
+        m.currency,
+        m.procedure_type,
+        &m.lots,
+        m.owner,
+        project_id
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    if rows == 0 {
+        return Err(AppError::NotFound(format!("Project id={} not found", project_id)));
+    }
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Normalize (trim, upper-case codes, dedupe lists) and validate metadata before saving.
+pub fn validate_metadata(metadata: &ProjectMetadata) -> Result<ProjectMetadata> {
+    fn clean(s: &Option<String>) -> Option<String> {
+        s.as_deref().map(str::trim).filter(|s| !s.is_empty()).map(str::to_string)
+    }
+
+    let notice_number = clean(&metadata.notice_number).map(|s| s.to_uppercase());
+    if let Some(n) = &notice_number {
+        if !RE_NOTICE.is_match(n) {
+            return Err(AppError::Validation(format!(
+                "Numărul anunțului SEAP/SICAP '{}' este invalid (ex. CN1045678)", n
+            )));
+        }
+    }
+
+    let mut cpv_codes: Vec<String> = Vec::new();
+    for code in &metadata.cpv_codes {
+        let code = code.trim();
+        if !RE_CPV.is_match(code) {
+            return Err(AppError::Validation(format!(
+                "Codul CPV '{}' este invalid (ex. 45233120-6)", code
+            )));
+        }
+        if !cpv_codes.iter().any(|c| c == code) {
+            cpv_codes.push(code.to_string());
+        }
+    }
+
+    if let Some(v) = metadata.estimated_value {
+        if v.is_sign_negative() {
+            return Err(AppError::Validation("Valoarea estimată nu poate fi negativă".into()));
+        }
+    }
+
+    let currency = clean(&metadata.currency).map(|s| s.to_uppercase());
+    match (&currency, metadata.estimated_value) {
+        (Some(c), _) if !RE_CURRENCY.is_match(c) => {
+            return Err(AppError::Validation(format!("Moneda '{}' este invalidă (ex. RON, EUR)", c)));
+        }
+        (None, Some(_)) => {
+            return Err(AppError::Validation("Valoarea estimată necesită o monedă".into()));
+        }
+        _ => {}
+    }
+
+    let procedure_type = clean(&metadata.procedure_type);
+    if let Some(p) = &procedure_type {
+        if !PROCEDURE_TYPES.contains(&p.as_str()) {
+            return Err(AppError::Validation(format!("Tip de procedură necunoscut: {}", p)));
+        }
+    }
+
+    let mut lots = metadata.lots.clone();
+    if lots.iter().any(|&l| l < 1) {
+        return Err(AppError::Validation("Numerele loturilor încep de la 1".into()));
+    }
+    lots.sort_unstable();
+    lots.dedup();
+
+    let owner = clean(&metadata.owner).map(|s| s.to_lowercase());
+    if let Some(o) = &owner {
+        if !o.contains('@') {
+            return Err(AppError::Validation("Adresa de e-mail a responsabilului este invalidă".into()));
+        }
+    }
+
+    Ok(ProjectMetadata {
+        contracting_authority: clean(&metadata.contracting_authority),
+        notice_number,
+        cpv_codes,
+        estimated_value: metadata.estimated_value,
+        currency,
+        procedure_type,
+        lots,
+        owner,
+    })
+}
+
+// ─────────────────────────── Clone Project ───────────────────────────
+
+/// Deep-copy a project (categories + full item tree) into `year` under a new title and
+/// date, e.g. when a cancelled tender is relaunched. The source is only read, so it may be
+/// in an archived (read-only) year. The folder path is never copied.
+pub async fn clone_project(
+    pool: &PgPool,
+    id: i32,
+    new_title: &str,
+    new_date: NaiveDate,
+    year: &str,
+    options: &CloneOptions,
+) -> Result<Project> {
+    let mut tx = pool.begin().await?;
+    guard::ensure_year_writable(&mut tx, year).await?;
+
+    let source = sqlx::query_as!(
+        Project,
+        "SELECT id, title, date, year, path, submission_deadline, clarification_deadline, opening_date,
+                contracting_authority, notice_number, cpv_codes, estimated_value, currency,
+                procedure_type, lots, owner, status
+         FROM projects WHERE id = $1 AND deleted_at IS NULL",
+        id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Project id={} not found", id)))?;
+
+    // Procurement metadata carries over; the relaunch gets a new notice and new deadlines
+    let project = sqlx::query_as!(
+        Project,
+        "INSERT INTO projects (title, date, year, contracting_authority, cpv_codes, estimated_value,
+                               currency, procedure_type, lots, owner)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+         RETURNING id, title, date, year, path, submission_deadline, clarification_deadline, opening_date,
+                contracting_authority, notice_number, cpv_codes, estimated_value, currency,
+                procedure_type, lots, owner, status",
+        new_title,
+        new_date,
+        year,
+        source.contracting_authority,
+        &source.cpv_codes,
+        source.estimated_value,
+        source.currency,
+        source.procedure_type,
+        &source.lots,
+        source.owner
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let categories = sqlx::query_scalar!(
+        "SELECT id FROM categories WHERE project_id = $1 ORDER BY sort_order, id",
+        id
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    // A kept spreadsheet keeps its import hash, sheet and profile, so it is checked for
+    // changes and re-imported like the source's
+    let mut cat_map: HashMap<i32, i32> = HashMap::new();
+    for source_cat_id in categories {
+        let cat_id = sqlx::query_scalar!(
+            r#"INSERT INTO categories (project_id, name, sort_order, excel_path, excel_hash,
+                                       excel_sheet, excel_profile_id)
+               SELECT $1, name, sort_order,
+                      CASE WHEN $3 THEN excel_path END, CASE WHEN $3 THEN excel_hash END,
+                      CASE WHEN $3 THEN excel_sheet END, CASE WHEN $3 THEN excel_profile_id END
+               FROM categories WHERE id = $2
+               RETURNING id AS "id!""#,
+            project.id, source_cat_id, options.keep_excel_path
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        cat_map.insert(source_cat_id, cat_id);
+    }
+
+    copy_checklist_items(&mut tx, &cat_map, options.keep_flags, options.keep_notes, 0).await?;
+
+    tx.commit().await?;
+    Ok(project)
+}
+
 // ─────────────────────────── Delete Project ───────────────────────────
 
 /// Fetch just the on-disk path for a project (if any).
@@ -253,15 +546,24 @@ pub async fn get_project_path(pool: &PgPool, id: i32) -> Result<Option<String>>
     Ok(row.and_then(|r| r.path))
 }
 
+/// Move a project to the trash (see `trash::restore_project`). Its categories,
+/// items and notes are kept untouched and come back with it.
 pub async fn delete_project(pool: &PgPool, id: i32) -> Result<()> {
-    let rows = sqlx::query!("DELETE FROM projects WHERE id = $1", id)
-        .execute(pool)
+    let mut tx = pool.begin().await?;
+    guard::ensure_project_writable(&mut tx, id).await?;
+
+    let rows = sqlx::query!(
+        "UPDATE projects SET deleted_at = now() WHERE id = $1 AND deleted_at IS NULL",
+        id
+    )
+        .execute(&mut *tx)
         .await?
         .rows_affected();
 
     if rows == 0 {
         return Err(AppError::NotFound(format!("Project id={} not found", id)));
     }
+    tx.commit().await?;
     Ok(())
 }
 
@@ -281,7 +583,10 @@ pub async fn save_project_folder(pool: &PgPool, id: i32, folder: &str) -> Result
 pub async fn list_projects_light(pool: &PgPool, year: &str) -> Result<Vec<Project>> {
     let projects = sqlx::query_as!(
         Project,
-        "SELECT id, title, date, year, path FROM projects WHERE year = $1",
+        "SELECT id, title, date, year, path, submission_deadline, clarification_deadline, opening_date,
+                contracting_authority, notice_number, cpv_codes, estimated_value, currency,
+                procedure_type, lots, owner, status
+         FROM projects WHERE year = $1 AND deleted_at IS NULL",
         year
     )
     .fetch_all(pool)
@@ -295,7 +600,7 @@ pub async fn list_years(pool: &PgPool) -> Result<Vec<String>> {
     // year column is NOT NULL, but sqlx infers Option for expressions.
     // Use "as year!" to force non-null.
     let years = sqlx::query_scalar!(
-        r#"SELECT DISTINCT year as "year!" FROM projects ORDER BY year"#
+        r#"SELECT DISTINCT year as "year!" FROM projects WHERE deleted_at IS NULL ORDER BY year"#
     )
     .fetch_all(pool)
     .await?;
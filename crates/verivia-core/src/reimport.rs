@@ -0,0 +1,434 @@
+//! Re-import an updated specification into a category without losing progress.
+//!
+//! Parsed requirements are matched with the existing items by reference (the numbering
+//! path, e.g. "2 › b") when the text is still similar, then by identical text, then by
+//! the most similar text. Matched items keep their flags, notes and response (filled
+//! from the specification only when empty); items whose text changed lose their
+//! verification and are flagged for review.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use sqlx::{PgConnection, PgPool};
+
+use crate::errors::{AppError, Result};
+use crate::excel;
+use crate::guard;
+use crate::import_profiles::get_import_profile;
+use crate::linked_excel;
+use crate::models::{ChangeKind, ChecklistDiff, ChecklistItemNested, ItemChange};
+use crate::tree::parents_first;
+
+/// Minimum word similarity for two texts with the same reference to be the same requirement.
+const SAME_REFERENCE_SIMILARITY: f64 = 0.3;
+/// Minimum word similarity for two texts without a common reference.
+const FUZZY_SIMILARITY: f64 = 0.6;
+
+// ─────────────────────────── Preview ───────────────────────────
+
+/// What re-importing `file_path` into the category would change. Nothing is written.
+/// `profile_id` selects a saved import profile; `None` detects the layout. `sheet_name`
+/// reads that sheet instead of the profile's (or the detected) one.
+pub async fn preview_reimport(
+    pool: &PgPool,
+    category_id: i32,
+    file_path: &str,
+    profile_id: Option<i32>,
+    sheet_name: Option<&str>,
+) -> Result<ChecklistDiff> {
+    let parsed = parse_file(pool, file_path, profile_id, sheet_name).await?;
+    let mut conn = pool.acquire().await?;
+    let existing = load_existing(&mut conn, category_id).await?;
+
+    let new_rows = flatten(&parsed);
+    let matches = match_items(&existing, &new_rows);
+    Ok(build_diff(&existing, &new_rows, &matches))
+}
+
+// ─────────────────────────── Apply ───────────────────────────
+
+/// Re-import `file_path` into the category in one transaction: matched items are moved
+/// and renamed in place (flags and notes kept), new requirements are inserted and the
+/// ones no longer in the specification go to the trash. The file becomes the category's
+/// Excel, with the sheet and profile it was read with (see `last_import_source`).
+pub async fn apply_reimport(
+    pool: &PgPool,
+    category_id: i32,
+    file_path: &str,
+    profile_id: Option<i32>,
+    sheet_name: Option<&str>,
+) -> Result<ChecklistDiff> {
+    let parsed = parse_file(pool, file_path, profile_id, sheet_name).await?;
+
+    let mut tx = pool.begin().await?;
+    guard::ensure_category_writable(&mut tx, category_id).await?;
+    let diff = merge_checklist(&mut tx, category_id, &parsed).await?;
+
+    sqlx::query!(
+        "UPDATE categories SET excel_path = $1, excel_hash = $2, excel_sheet = $3, excel_profile_id = $4
+         WHERE id = $5",
+        file_path, linked_excel::file_hash(file_path).ok(), sheet_name, profile_id, category_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(diff)
+}
+
+/// The sheet and import profile the category's spreadsheet was last imported with
+/// (`None` for the detected ones), to read it the same way again.
+pub async fn last_import_source(pool: &PgPool, category_id: i32) -> Result<(Option<String>, Option<i32>)> {
+    let source = sqlx::query!(
+        "SELECT excel_sheet, excel_profile_id FROM categories WHERE id = $1",
+        category_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Category id={} not found", category_id)))?;
+    Ok((source.excel_sheet, source.excel_profile_id))
+}
+
+/// Merge `parsed` into the category's checklist as described for `apply_reimport`,
+/// inside the caller's transaction (the caller runs the write guard).
+pub(crate) async fn merge_checklist(
+    conn: &mut PgConnection,
+    category_id: i32,
+    parsed: &[ChecklistItemNested],
+) -> Result<ChecklistDiff> {
+    let existing = load_existing(conn, category_id).await?;
+    let new_rows = flatten(parsed);
+    let matches = match_items(&existing, &new_rows);
+
+    // Rows are in document order, so every parent already has its id
+    let mut ids: Vec<i32> = Vec::with_capacity(new_rows.len());
+    for (row, matched) in new_rows.iter().zip(&matches) {
+        let parent_id = row.parent.map(|p| ids[p]);
+        let id = match matched {
+            Some((e, similarity)) => {
+                let renamed = *similarity < 1.0;
+                sqlx::query!(
+                    "UPDATE checklist_items SET parent_id = $2, sort_order = $3, name = $4, reference = $5,
+                         response = COALESCE(response, $7), document_reference = COALESCE(document_reference, $8),
+                         needs_review = needs_review OR $6,
+                         previous_name = CASE WHEN $6 THEN COALESCE(previous_name, name) ELSE previous_name END,
+                         verified = verified AND NOT $6,
+                         verified_by = CASE WHEN $6 THEN NULL ELSE verified_by END,
+                         verified_at = CASE WHEN $6 THEN NULL ELSE verified_at END
+                     WHERE id = $1",
+                    existing[*e].id, parent_id, row.sort_order, row.item.name, row.item.reference, renamed,
+                    row.item.response, row.item.document_reference
+                )
+                .execute(&mut *conn)
+                .await?;
+                existing[*e].id
+            }
+            None => {
+                sqlx::query_scalar!(
+                    "INSERT INTO checklist_items (category_id, parent_id, name, sort_order, reference,
+                                                  response, document_reference)
+                     VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING id",
+                    category_id, parent_id, row.item.name, row.sort_order, row.item.reference,
+                    row.item.response, row.item.document_reference
+                )
+                .fetch_one(&mut *conn)
+                .await?
+            }
+        };
+        ids.push(id);
+    }
+
+    let matched: HashSet<usize> = matches.iter().flatten().map(|(e, _)| *e).collect();
+    let removed: Vec<i32> = (0..existing.len())
+        .filter(|e| !matched.contains(e))
+        .map(|e| existing[e].id)
+        .collect();
+    sqlx::query!(
+        "UPDATE checklist_items SET deleted_at = now() WHERE id = ANY($1)",
+        &removed
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(build_diff(&existing, &new_rows, &matches))
+}
+
+// ─────────────────────────── Rows ───────────────────────────
+
+/// An item of the current checklist, in document order.
+struct ExistingRow {
+    id: i32,
+    name: String,
+    reference: Option<String>,
+    proposed: bool,
+    verified: bool,
+    notes: i64,
+    depth: usize,
+    /// References from the top level down to this item
+    reference_path: String,
+}
+
+/// A requirement of the parsed specification, in document order.
+struct NewRow<'a> {
+    item: &'a ChecklistItemNested,
+    /// Index of the parent row
+    parent: Option<usize>,
+    sort_order: i32,
+    depth: usize,
+    reference_path: String,
+}
+
+async fn parse_file(
+    pool: &PgPool,
+    file_path: &str,
+    profile_id: Option<i32>,
+    sheet_name: Option<&str>,
+) -> Result<Vec<ChecklistItemNested>> {
+    match (profile_id, sheet_name) {
+        (Some(id), sheet) => {
+            let mut profile = get_import_profile(pool, id).await?;
+            if let Some(sheet) = sheet {
+                profile.sheet_name = Some(sheet.to_string());
+            }
+            excel::parse_excel(file_path, &profile)
+        }
+        (None, Some(sheet)) => excel::parse_excel(file_path, &excel::detect_layout(file_path, Some(sheet))?.profile()),
+        (None, None) => excel::parse_technical_excel(file_path),
+    }
+}
+
+async fn load_existing(conn: &mut PgConnection, category_id: i32) -> Result<Vec<ExistingRow>> {
+    let items = sqlx::query!(
+        r#"SELECT i.id, i.parent_id, i.name, i.reference, i.proposed, i.verified,
+                  (SELECT COUNT(*) FROM notes n WHERE n.item_id = i.id AND n.deleted_at IS NULL) AS "notes!"
+           FROM checklist_items i
+           WHERE i.category_id = $1 AND i.deleted_at IS NULL
+           ORDER BY i.sort_order, i.id"#,
+        category_id
+    )
+    .fetch_all(conn)
+    .await?;
+
+    // (depth, reference path) of the rows placed so far, by id
+    let mut placed: HashMap<i32, (usize, String)> = HashMap::new();
+    let mut rows = Vec::with_capacity(items.len());
+    for item in parents_first(&items, |i| (i.id, i.parent_id)) {
+        let (depth, reference_path) = match item.parent_id.and_then(|p| placed.get(&p)) {
+            Some((depth, path)) => (depth + 1, extend_path(path, item.reference.as_deref())),
+            None => (0, extend_path("", item.reference.as_deref())),
+        };
+        placed.insert(item.id, (depth, reference_path.clone()));
+        rows.push(ExistingRow {
+            id: item.id,
+            name: item.name.clone(),
+            reference: item.reference.clone(),
+            proposed: item.proposed,
+            verified: item.verified,
+            notes: item.notes,
+            depth,
+            reference_path,
+        });
+    }
+    Ok(rows)
+}
+
+/// The parsed tree as rows in document order (parents before their children).
+fn flatten(items: &[ChecklistItemNested]) -> Vec<NewRow<'_>> {
+    let mut rows: Vec<NewRow> = Vec::new();
+    let mut stack: Vec<(Option<usize>, i32, &ChecklistItemNested)> = items
+        .iter()
+        .enumerate()
+        .rev()
+        .map(|(i, item)| (None, i as i32, item))
+        .collect();
+
+    while let Some((parent, sort_order, item)) = stack.pop() {
+        let (depth, parent_path) = match parent {
+            Some(p) => (rows[p].depth + 1, rows[p].reference_path.as_str()),
+            None => (0, ""),
+        };
+        let reference_path = extend_path(parent_path, item.reference.as_deref());
+        rows.push(NewRow { item, parent, sort_order, depth, reference_path });
+
+        let index = rows.len() - 1;
+        stack.extend(item.sub_tasks.iter().enumerate().rev().map(|(j, sub)| (Some(index), j as i32, sub)));
+    }
+    rows
+}
+
+// ─────────────────────────── Matching ───────────────────────────
+
+/// For every new row, the matched existing row and the text similarity.
+fn match_items(existing: &[ExistingRow], new_rows: &[NewRow]) -> Vec<Option<(usize, f64)>> {
+    let existing_words: Vec<Vec<String>> = existing.iter().map(|e| text_words(&e.name)).collect();
+    let new_words: Vec<Vec<String>> = new_rows.iter().map(|n| text_words(&n.item.name)).collect();
+
+    let mut matches: Vec<Option<(usize, f64)>> = vec![None; new_rows.len()];
+    let mut taken = vec![false; existing.len()];
+
+    // 1. Same reference path and a similar text, paired in document order
+    let mut by_reference: HashMap<&str, VecDeque<usize>> = HashMap::new();
+    for (e, row) in existing.iter().enumerate() {
+        if row.reference.is_some() {
+            by_reference.entry(row.reference_path.as_str()).or_default().push_back(e);
+        }
+    }
+    for (n, row) in new_rows.iter().enumerate() {
+        if row.item.reference.is_none() {
+            continue;
+        }
+        let Some(candidates) = by_reference.get_mut(row.reference_path.as_str()) else { continue };
+        if let Some(e) = candidates.pop_front() {
+            let score = similarity(&existing_words[e], &new_words[n]);
+            if score >= SAME_REFERENCE_SIMILARITY {
+                matches[n] = Some((e, score));
+                taken[e] = true;
+            }
+        }
+    }
+
+    // 2. Same text anywhere (renumbered or moved)
+    let mut by_text: HashMap<String, VecDeque<usize>> = HashMap::new();
+    for e in (0..existing.len()).filter(|&e| !taken[e]) {
+        by_text.entry(existing_words[e].join(" ")).or_default().push_back(e);
+    }
+    for n in 0..new_rows.len() {
+        if matches[n].is_some() {
+            continue;
+        }
+        if let Some(e) = by_text.get_mut(&new_words[n].join(" ")).and_then(VecDeque::pop_front) {
+            matches[n] = Some((e, 1.0));
+            taken[e] = true;
+        }
+    }
+
+    // 3. Most similar remaining texts first
+    let mut pairs: Vec<(f64, usize, usize)> = Vec::new();
+    for n in (0..new_rows.len()).filter(|&n| matches[n].is_none()) {
+        for e in (0..existing.len()).filter(|&e| !taken[e]) {
+            let score = similarity(&existing_words[e], &new_words[n]);
+            if score >= FUZZY_SIMILARITY {
+                pairs.push((score, n, e));
+            }
+        }
+    }
+    pairs.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)));
+    for (score, n, e) in pairs {
+        if matches[n].is_none() && !taken[e] {
+            matches[n] = Some((e, score));
+            taken[e] = true;
+        }
+    }
+
+    matches
+}
+
+fn build_diff(existing: &[ExistingRow], new_rows: &[NewRow], matches: &[Option<(usize, f64)>]) -> ChecklistDiff {
+    let mut diff = ChecklistDiff::default();
+
+    for (row, matched) in new_rows.iter().zip(matches) {
+        let change = match *matched {
+            Some((e, similarity)) => {
+                let old = &existing[e];
+                ItemChange {
+                    kind: if similarity < 1.0 { ChangeKind::Renamed } else { ChangeKind::Unchanged },
+                    item_id: Some(old.id),
+                    depth: row.depth,
+                    reference: row.item.reference.clone(),
+                    name: row.item.name.clone(),
+                    previous_reference: Some(old.reference.clone()).filter(|r| *r != row.item.reference).flatten(),
+                    previous_name: Some(old.name.clone()).filter(|n| *n != row.item.name),
+                    similarity: round(similarity),
+                    proposed: old.proposed,
+                    verified: old.verified && similarity >= 1.0,
+                    notes: old.notes,
+                }
+            }
+            None => ItemChange {
+                kind: ChangeKind::Added,
+                item_id: None,
+                depth: row.depth,
+                reference: row.item.reference.clone(),
+                name: row.item.name.clone(),
+                previous_reference: None,
+                previous_name: None,
+                similarity: 0.0,
+                proposed: false,
+                verified: false,
+                notes: 0,
+            },
+        };
+        diff.changes.push(change);
+    }
+
+    let matched: HashSet<usize> = matches.iter().flatten().map(|(e, _)| *e).collect();
+    for (_, old) in existing.iter().enumerate().filter(|(e, _)| !matched.contains(e)) {
+        diff.changes.push(ItemChange {
+            kind: ChangeKind::Removed,
+            item_id: Some(old.id),
+            depth: old.depth,
+            reference: old.reference.clone(),
+            name: old.name.clone(),
+            previous_reference: None,
+            previous_name: None,
+            similarity: 0.0,
+            proposed: old.proposed,
+            verified: old.verified,
+            notes: old.notes,
+        });
+    }
+
+    for change in &diff.changes {
+        match change.kind {
+            ChangeKind::Added => diff.added += 1,
+            ChangeKind::Removed => diff.removed += 1,
+            ChangeKind::Renamed => diff.renamed += 1,
+            ChangeKind::Unchanged => diff.unchanged += 1,
+        }
+    }
+    diff
+}
+
+// ─────────────────────────── Helpers ───────────────────────────
+
+/// "2" + "b)" → "2 › b": references compared without their punctuation.
+fn extend_path(parent_path: &str, reference: Option<&str>) -> String {
+    let reference = reference
+        .unwrap_or("")
+        .trim()
+        .trim_matches(|c: char| matches!(c, '(' | ')' | '.'))
+        .to_lowercase();
+    if parent_path.is_empty() {
+        reference
+    } else {
+        format!("{} › {}", parent_path, reference)
+    }
+}
+
+fn text_words(text: &str) -> Vec<String> {
+    excel::words(text).collect()
+}
+
+/// Dice coefficient of the two word multisets: 1 only for the same words in the same
+/// order (case, diacritics and punctuation ignored), 0 for no word in common.
+fn similarity(a: &[String], b: &[String]) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+    let mut counts: HashMap<&str, i32> = HashMap::new();
+    for w in a {
+        *counts.entry(w.as_str()).or_default() += 1;
+    }
+    let mut common = 0;
+    for w in b {
+        if let Some(c) = counts.get_mut(w.as_str()).filter(|c| **c > 0) {
+            *c -= 1;
+            common += 1;
+        }
+    }
+    // Same words, reordered: still a changed text
+    (2.0 * common as f64 / (a.len() + b.len()) as f64).min(0.99)
+}
+
+fn round(score: f64) -> f64 {
+    (score * 100.0).round() / 100.0
+}
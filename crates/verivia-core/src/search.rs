@@ -0,0 +1,80 @@
+use sqlx::PgPool;
+
+use crate::errors::{AppError, Result};
+use crate::models::SearchHit;
+
+const DEFAULT_LIMIT: i64 = 50;
+
+// ─────────────────────────── Full-Text Search ───────────────────────────
+// Uses the `romanian_unaccent` configuration and the generated `search_vector`
+// columns from migration 014 (project titles, item names, note texts).
+
+/// Ranked matches for `text` across project titles, checklist items and notes.
+/// Every word must match (as a prefix, diacritics ignored). `year = None` searches all years.
+pub async fn search(
+    pool: &PgPool,
+    text: &str,
+    year: Option<&str>,
+    limit: Option<i64>,
+) -> Result<Vec<SearchHit>> {
+    let query = prefix_tsquery(text)
+        .ok_or_else(|| AppError::Validation("Textul de căutat nu poate fi gol".into()))?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, 500);
+
+    let hits = sqlx::query_as!(
+        SearchHit,
+        r#"WITH q AS (SELECT to_tsquery('romanian_unaccent', $1) AS q)
+           SELECT 'project' AS "kind!", p.id AS "project_id!", p.title AS "project_title!",
+                  p.year AS "year!", NULL::text AS category, NULL::int AS item_id,
+                  NULL::text AS item_name,
+                  ts_headline('romanian_unaccent', p.title, q.q) AS "snippet!",
+                  ts_rank(p.search_vector, q.q) AS "rank!"
+           FROM projects p, q
+           WHERE p.search_vector @@ q.q AND p.deleted_at IS NULL
+             AND ($2::text IS NULL OR p.year = $2)
+           UNION ALL
+           SELECT 'item', p.id, p.title, p.year, c.name, i.id, i.name,
+                  ts_headline('romanian_unaccent', i.name, q.q),
+                  ts_rank(i.search_vector, q.q)
+           FROM checklist_items i
+           JOIN categories c ON c.id = i.category_id
+           JOIN projects p ON p.id = c.project_id, q
+           WHERE i.search_vector @@ q.q AND i.deleted_at IS NULL AND p.deleted_at IS NULL
+             AND ($2::text IS NULL OR p.year = $2)
+           UNION ALL
+           SELECT 'note', p.id, p.title, p.year, c.name, i.id, i.name,
+                  ts_headline('romanian_unaccent', n.text, q.q, 'MaxFragments=2'),
+                  ts_rank(n.search_vector, q.q)
+           FROM notes n
+           JOIN checklist_items i ON i.id = n.item_id
+           JOIN categories c ON c.id = i.category_id
+           JOIN projects p ON p.id = c.project_id, q
+           WHERE n.search_vector @@ q.q AND n.deleted_at IS NULL AND i.deleted_at IS NULL
+             AND p.deleted_at IS NULL AND ($2::text IS NULL OR p.year = $2)
+           ORDER BY 9 DESC, 2 DESC
+           LIMIT $3"#,
+        query,
+        year,
+        limit
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(hits)
+}
+
+/// "garantie banca" → "garantie:* & banca:*". Punctuation is dropped so user
+/// input can never produce tsquery syntax errors. `None` if no words remain.
+fn prefix_tsquery(text: &str) -> Option<String> {
+    let terms: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| format!("{}:*", w))
+        .collect();
+
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" & "))
+    }
+}
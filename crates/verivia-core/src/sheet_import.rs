@@ -0,0 +1,124 @@
+//! Import a workbook whose sheets hold different categories (eligibility, financial,
+//! technical, ...) into the matching categories of a project.
+
+use std::collections::HashSet;
+
+use sqlx::PgPool;
+
+use crate::checklist::get_category_id;
+use crate::errors::{AppError, Result};
+use crate::excel;
+use crate::guard;
+use crate::import_profiles::get_import_profile;
+use crate::linked_excel;
+use crate::models::{ChecklistItemNested, ImportProfile, SheetImport, SheetMapping};
+use crate::reimport::merge_checklist;
+
+// ─────────────────────────── Preview ───────────────────────────
+
+/// The checklist each mapped sheet would produce. Nothing is written.
+pub async fn preview_sheet_import(
+    pool: &PgPool,
+    project_id: i32,
+    file_path: &str,
+    mappings: &[SheetMapping],
+) -> Result<Vec<SheetImport>> {
+    let (_, imports) = parse_mappings(pool, project_id, file_path, mappings).await?;
+    Ok(imports)
+}
+
+// ─────────────────────────── Import ───────────────────────────
+
+/// Import every mapped sheet into its category, in one transaction: either all
+/// categories are imported or none is. A category that already has items is merged like
+/// a re-import (`reimport::merge_checklist`), so unchanged requirements keep their flags,
+/// notes and responses and the ones no longer in the sheet go to the trash. The workbook
+/// is linked to each category with its sheet and profile, for change checks.
+pub async fn import_sheets(
+    pool: &PgPool,
+    project_id: i32,
+    file_path: &str,
+    mappings: &[SheetMapping],
+) -> Result<Vec<SheetImport>> {
+    let (category_ids, imports) = parse_mappings(pool, project_id, file_path, mappings).await?;
+
+    let hash = linked_excel::file_hash(file_path).ok();
+    let mut tx = pool.begin().await?;
+    for &category_id in &category_ids {
+        guard::ensure_category_writable(&mut tx, category_id).await?;
+    }
+    for ((&category_id, import), mapping) in category_ids.iter().zip(&imports).zip(mappings) {
+        merge_checklist(&mut tx, category_id, &import.checklist).await?;
+
+        sqlx::query!(
+            "UPDATE categories SET excel_path = $1, excel_hash = $2, excel_sheet = $3, excel_profile_id = $4
+             WHERE id = $5",
+            file_path, hash, mapping.sheet_name, mapping.profile_id, category_id
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+
+    Ok(imports)
+}
+
+// ─────────────────────────── Helpers ───────────────────────────
+
+/// Resolve categories and layouts, then parse every mapped sheet.
+/// Returns the category ids in mapping order with the parsed checklists.
+async fn parse_mappings(
+    pool: &PgPool,
+    project_id: i32,
+    file_path: &str,
+    mappings: &[SheetMapping],
+) -> Result<(Vec<i32>, Vec<SheetImport>)> {
+    if mappings.is_empty() {
+        return Err(AppError::Validation("Alegeți cel puțin o foaie de importat".into()));
+    }
+
+    let mut seen = HashSet::new();
+    let mut category_ids = Vec::with_capacity(mappings.len());
+    let mut profiles = Vec::with_capacity(mappings.len());
+    for m in mappings {
+        if !seen.insert(m.category_name.trim().to_lowercase()) {
+            return Err(AppError::Validation(format!(
+                "Categoria '{}' este aleasă pentru mai multe foi",
+                m.category_name
+            )));
+        }
+        category_ids.push(get_category_id(pool, project_id, m.category_name.trim()).await?);
+        profiles.push(sheet_profile(pool, file_path, m).await?);
+    }
+
+    let parsed = excel::parse_sheets(file_path, &profiles)?;
+
+    let imports = mappings
+        .iter()
+        .zip(parsed)
+        .map(|(m, parsed)| SheetImport {
+            sheet_name: m.sheet_name.clone(),
+            category_name: m.category_name.clone(),
+            item_count: count_items(&parsed.checklist),
+            checklist: parsed.checklist,
+            report: parsed.report,
+        })
+        .collect();
+
+    Ok((category_ids, imports))
+}
+
+/// The saved profile pointed at the mapped sheet, or the layout detected on it.
+async fn sheet_profile(pool: &PgPool, file_path: &str, mapping: &SheetMapping) -> Result<ImportProfile> {
+    match mapping.profile_id {
+        Some(id) => Ok(ImportProfile {
+            sheet_name: Some(mapping.sheet_name.clone()),
+            ..get_import_profile(pool, id).await?
+        }),
+        None => Ok(excel::detect_layout(file_path, Some(&mapping.sheet_name))?.profile()),
+    }
+}
+
+fn count_items(items: &[ChecklistItemNested]) -> usize {
+    items.iter().map(|i| 1 + count_items(&i.sub_tasks)).sum()
+}
@@ -0,0 +1,233 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{Duration, Utc};
+use sqlx::PgPool;
+
+use crate::errors::{AppError, Result};
+use crate::models::{CategoryStats, ItemCounts, ProjectStats, UserActivity, YearStats};
+
+/// A project in preparation is at risk when its submission deadline is this close (or past)
+/// and its checklist is not complete.
+pub const AT_RISK_DAYS: i64 = 7;
+
+// ─────────────────────────── Year / Project Stats ───────────────────────────
+
+pub async fn year_stats(pool: &PgPool, year: &str) -> Result<YearStats> {
+    let (projects, lead_time) = collect(pool, Some(year), None).await?;
+
+    let mut counts = ItemCounts::default();
+    let mut by_category: Vec<(String, ItemCounts)> = Vec::new();
+    let mut by_user: BTreeMap<String, (i64, i64)> = BTreeMap::new();
+    for p in &projects {
+        add_counts(&mut counts, &p.counts);
+        for c in &p.categories {
+            // Keep first-seen order (categories come in sort_order)
+            match by_category.iter_mut().find(|(name, _)| name == &c.name) {
+                Some((_, total)) => add_counts(total, &c.counts),
+                None => by_category.push((c.name.clone(), c.counts.clone())),
+            }
+        }
+        for u in &p.users {
+            let entry = by_user.entry(u.user.clone()).or_default();
+            entry.0 += u.proposed;
+            entry.1 += u.verified;
+        }
+    }
+    finish_counts(&mut counts);
+
+    let categories = by_category
+        .into_iter()
+        .map(|(name, mut counts)| {
+            finish_counts(&mut counts);
+            CategoryStats { name, counts }
+        })
+        .collect();
+
+    let users = by_user
+        .into_iter()
+        .map(|(user, (proposed, verified))| UserActivity { user, proposed, verified })
+        .collect();
+
+    let risk_limit = Utc::now() + Duration::days(AT_RISK_DAYS);
+    let at_risk = projects
+        .iter()
+        .filter(|p| {
+            p.status == "in_preparation"
+                && p.counts.complete < p.counts.total
+                && p.submission_deadline.is_some_and(|d| d <= risk_limit)
+        })
+        .cloned()
+        .collect();
+
+    Ok(YearStats {
+        year: year.to_string(),
+        project_count: projects.len() as i64,
+        counts,
+        categories,
+        avg_verification_hours: lead_time.average(),
+        users,
+        projects,
+        at_risk,
+    })
+}
+
+pub async fn project_stats(pool: &PgPool, project_id: i32) -> Result<ProjectStats> {
+    let (projects, _) = collect(pool, None, Some(project_id)).await?;
+    projects
+        .into_iter()
+        .next()
+        .ok_or_else(|| AppError::NotFound(format!("Project id={} not found", project_id)))
+}
+
+// ─────────────────────────── Aggregation (3 queries) ───────────────────────────
+
+/// Sum of proposal → verification lead times, in seconds.
+#[derive(Default)]
+struct LeadTime {
+    seconds: f64,
+    items: i64,
+}
+
+impl LeadTime {
+    fn average(&self) -> Option<f64> {
+        (self.items > 0).then(|| self.seconds / self.items as f64 / 3600.0)
+    }
+}
+
+/// Per-project stats for a year or a single project, newest project first.
+/// Also returns the lead time summed over all of them.
+async fn collect(
+    pool: &PgPool,
+    year: Option<&str>,
+    project_id: Option<i32>,
+) -> Result<(Vec<ProjectStats>, LeadTime)> {
+    // 1) Item counters per project × category (projects without categories included)
+    let rows = sqlx::query!(
+        r#"SELECT p.id, p.title, p.status, p.submission_deadline, c.name AS "category?",
+                  COUNT(i.id) AS "total!",
+                  COUNT(i.id) FILTER (WHERE i.proposed) AS "proposed!",
+                  COUNT(i.id) FILTER (WHERE i.verified) AS "verified!",
+                  COUNT(i.id) FILTER (WHERE i.proposed AND i.verified) AS "complete!"
+           FROM projects p
+           LEFT JOIN categories c ON c.project_id = p.id
+           LEFT JOIN checklist_items i ON i.category_id = c.id AND i.deleted_at IS NULL
+           WHERE p.deleted_at IS NULL
+             AND ($1::text IS NULL OR p.year = $1) AND ($2::int IS NULL OR p.id = $2)
+           GROUP BY p.id, c.id
+           ORDER BY p.date DESC, p.id DESC, c.sort_order, c.id"#,
+        year,
+        project_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    // 2) Lead times per project
+    let lead_rows = sqlx::query!(
+        r#"SELECT c.project_id,
+                  SUM(EXTRACT(EPOCH FROM i.verified_at - i.proposed_at))::float8 AS "seconds!",
+                  COUNT(*) AS "items!"
+           FROM checklist_items i
+           JOIN categories c ON c.id = i.category_id
+           JOIN projects p ON p.id = c.project_id
+           WHERE p.deleted_at IS NULL AND i.deleted_at IS NULL
+             AND ($1::text IS NULL OR p.year = $1) AND ($2::int IS NULL OR p.id = $2)
+             AND i.proposed AND i.verified AND i.verified_at >= i.proposed_at
+           GROUP BY c.project_id"#,
+        year,
+        project_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    // 3) Flags set per user
+    let user_rows = sqlx::query!(
+        r#"SELECT c.project_id, u.name AS "user!",
+                  COUNT(*) FILTER (WHERE u.flag = 'proposed') AS "proposed!",
+                  COUNT(*) FILTER (WHERE u.flag = 'verified') AS "verified!"
+           FROM checklist_items i
+           JOIN categories c ON c.id = i.category_id
+           JOIN projects p ON p.id = c.project_id
+           CROSS JOIN LATERAL (VALUES
+               (CASE WHEN i.proposed THEN i.proposed_by END, 'proposed'),
+               (CASE WHEN i.verified THEN i.verified_by END, 'verified')
+           ) AS u(name, flag)
+           WHERE p.deleted_at IS NULL AND i.deleted_at IS NULL
+             AND ($1::text IS NULL OR p.year = $1) AND ($2::int IS NULL OR p.id = $2)
+             AND u.name IS NOT NULL
+           GROUP BY c.project_id, u.name
+           ORDER BY u.name"#,
+        year,
+        project_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    // ─── Assemble in memory ───
+
+    let mut total_lead = LeadTime::default();
+    let mut lead_by_project: HashMap<i32, LeadTime> = HashMap::new();
+    for r in lead_rows {
+        total_lead.seconds += r.seconds;
+        total_lead.items += r.items;
+        lead_by_project.insert(r.project_id, LeadTime { seconds: r.seconds, items: r.items });
+    }
+
+    let mut users_by_project: HashMap<i32, Vec<UserActivity>> = HashMap::new();
+    for r in user_rows {
+        users_by_project.entry(r.project_id).or_default().push(UserActivity {
+            user: r.user,
+            proposed: r.proposed,
+            verified: r.verified,
+        });
+    }
+
+    let mut projects: Vec<ProjectStats> = Vec::new();
+    for r in rows {
+        if projects.last().map(|p| p.project_id) != Some(r.id) {
+            projects.push(ProjectStats {
+                project_id: r.id,
+                title: r.title,
+                status: r.status,
+                submission_deadline: r.submission_deadline,
+                counts: ItemCounts::default(),
+                categories: Vec::new(),
+                avg_verification_hours: lead_by_project.get(&r.id).and_then(LeadTime::average),
+                users: users_by_project.remove(&r.id).unwrap_or_default(),
+            });
+        }
+        let Some(name) = r.category else { continue };
+
+        let mut counts = ItemCounts {
+            total: r.total,
+            proposed: r.proposed,
+            verified: r.verified,
+            complete: r.complete,
+            completion_pct: 0.0,
+        };
+        finish_counts(&mut counts);
+
+        let project = projects.last_mut().expect("pushed above");
+        add_counts(&mut project.counts, &counts);
+        project.categories.push(CategoryStats { name, counts });
+    }
+    for p in &mut projects {
+        finish_counts(&mut p.counts);
+    }
+
+    Ok((projects, total_lead))
+}
+
+fn add_counts(into: &mut ItemCounts, from: &ItemCounts) {
+    into.total += from.total;
+    into.proposed += from.proposed;
+    into.verified += from.verified;
+    into.complete += from.complete;
+}
+
+fn finish_counts(counts: &mut ItemCounts) {
+    counts.completion_pct = if counts.total == 0 {
+        100.0
+    } else {
+        (counts.complete as f64 * 1000.0 / counts.total as f64).round() / 10.0
+    };
+}
@@ -0,0 +1,132 @@
+use sqlx::PgPool;
+
+use crate::errors::{AppError, Result};
+use crate::guard;
+use crate::locks;
+use crate::models::StatusChange;
+
+/// Lifecycle statuses of a project, in pipeline order. New projects start in `in_preparation`.
+pub const PROJECT_STATUSES: &[&str] = &[
+    "in_preparation",
+    "submitted",
+    "won",
+    "lost",
+    "cancelled",
+    "under_appeal",
+];
+
+/// Statuses a project may move to from `from`.
+/// An award (won/lost) or a cancellation can be appealed; the appeal is resolved
+/// by re-evaluation (back to submitted) or by a new award / cancellation.
+pub fn allowed_transitions(from: &str) -> &'static [&'static str] {
+    match from {
+        "in_preparation" => &["submitted", "cancelled"],
+        "submitted" => &["won", "lost", "cancelled", "under_appeal"],
+        "won" | "lost" | "cancelled" => &["under_appeal"],
+        "under_appeal" => &["submitted", "won", "lost", "cancelled"],
+        _ => &[],
+    }
+}
+
+// ─────────────────────────── Change Status ───────────────────────────
+
+/// Move a project to `new_status` and log the transition.
+///
+/// A project can only be marked `submitted` when every category is complete
+/// (all items proposed and verified). Otherwise `override_reason` must explain
+/// why it was submitted anyway; the reason is stored in the history.
+/// Submitting also locks the project's checklist (see locks.rs).
+pub async fn set_project_status(
+    pool: &PgPool,
+    project_id: i32,
+    new_status: &str,
+    user: &str,
+    override_reason: Option<&str>,
+) -> Result<()> {
+    if !PROJECT_STATUSES.contains(&new_status) {
+        return Err(AppError::Validation(format!("Status necunoscut: '{}'", new_status)));
+    }
+    let override_reason = override_reason.map(str::trim).filter(|r| !r.is_empty());
+
+    let mut tx = pool.begin().await?;
+    guard::ensure_project_writable(&mut tx, project_id).await?;
+
+    let current = sqlx::query_scalar!(
+        "SELECT status FROM projects WHERE id = $1 AND deleted_at IS NULL FOR UPDATE",
+        project_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Project id={} not found", project_id)))?;
+
+    if !allowed_transitions(&current).contains(&new_status) {
+        return Err(AppError::Validation(format!(
+            "Tranziția {} → {} nu este permisă",
+            current, new_status
+        )));
+    }
+
+    let mut recorded_override = None;
+    if new_status == "submitted" {
+        let incomplete = sqlx::query_scalar!(
+            r#"SELECT DISTINCT c.name AS "v!" FROM categories c
+               JOIN checklist_items i ON i.category_id = c.id
+               WHERE c.project_id = $1 AND i.deleted_at IS NULL AND NOT (i.proposed AND i.verified)
+               ORDER BY 1"#,
+            project_id
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        if !incomplete.is_empty() {
+            match override_reason {
+                Some(reason) => recorded_override = Some(reason),
+                None => {
+                    return Err(AppError::Validation(format!(
+                        "Proiectul nu poate fi marcat ca depus: categorii incomplete ({}). \
+                         Indicați un motiv pentru a depune oricum.",
+                        incomplete.join(", ")
+                    )));
+                }
+            }
+        }
+    }
+
+    sqlx::query!(
+        "UPDATE projects SET status = $1, status_changed_at = now() WHERE id = $2",
+        new_status, project_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        "INSERT INTO project_status_history (project_id, from_status, to_status, changed_by, override_reason)
+         VALUES ($1, $2, $3, $4, $5)",
+        project_id, current, new_status, user, recorded_override
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    if new_status == "submitted" {
+        locks::lock_on_submit(&mut tx, project_id, user).await?;
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+// ─────────────────────────── History ───────────────────────────
+
+/// All status transitions of a project, oldest first.
+pub async fn status_history(pool: &PgPool, project_id: i32) -> Result<Vec<StatusChange>> {
+    let history = sqlx::query_as!(
+        StatusChange,
+        "SELECT from_status, to_status, changed_by, changed_at, override_reason
+         FROM project_status_history WHERE project_id = $1 ORDER BY changed_at, id",
+        project_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(history)
+}
@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+
+use sqlx::{PgPool, Postgres, Transaction};
+
+use crate::errors::{AppError, Result};
+use crate::models::{ProjectTemplate, TemplateCategory, TemplateItem};
+use crate::tree::parents_first;
+
+/// Flat row of an item tree (template items or checklist items), grouped by category.
+struct TreeRow {
+    id: i32,
+    group_id: i32,
+    parent_id: Option<i32>,
+    name: String,
+    sort_order: i32,
+}
+
+// ─────────────────────────── List Templates (3 queries total) ───────────────────────────
+
+pub async fn list_project_templates(pool: &PgPool) -> Result<Vec<ProjectTemplate>> {
+    let templates = sqlx::query!(
+        "SELECT id, name, description FROM project_templates ORDER BY name"
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let categories = sqlx::query!(
+        "SELECT id, template_id, name FROM template_categories ORDER BY sort_order, id"
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let items = sqlx::query_as!(
+        TreeRow,
+        r#"SELECT id, template_category_id AS "group_id!", parent_id, name, sort_order
+           FROM template_items ORDER BY sort_order, id"#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    // ─── Assemble in memory ───
+
+    let mut children_by_parent: HashMap<i32, Vec<&TreeRow>> = HashMap::new();
+    let mut top_items_by_cat: HashMap<i32, Vec<&TreeRow>> = HashMap::new();
+    for item in &items {
+        match item.parent_id {
+            Some(pid) => children_by_parent.entry(pid).or_default().push(item),
+            None => top_items_by_cat.entry(item.group_id).or_default().push(item),
+        }
+    }
+
+    let mut cats_by_template: HashMap<i32, Vec<TemplateCategory>> = HashMap::new();
+    for cat in &categories {
+        let items = top_items_by_cat
+            .get(&cat.id)
+            .map(|top| top.iter().map(|i| build_template_item(i, &children_by_parent)).collect())
+            .unwrap_or_default();
+
+        cats_by_template
+            .entry(cat.template_id)
+            .or_default()
+            .push(TemplateCategory {
+                name: cat.name.clone(),
+                items,
+            });
+    }
+
+    let result = templates
+        .into_iter()
+        .map(|t| ProjectTemplate {
+            id: t.id,
+            name: t.name,
+            description: t.description,
+            categories: cats_by_template.remove(&t.id).unwrap_or_default(),
+        })
+        .collect();
+
+    Ok(result)
+}
+
+fn build_template_item(
+    item: &TreeRow,
+    children_by_parent: &HashMap<i32, Vec<&TreeRow>>,
+) -> TemplateItem {
+    let sub_tasks = children_by_parent
+        .get(&item.id)
+        .map(|children| {
+            children
+                .iter()
+                .map(|c| build_template_item(c, children_by_parent))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    TemplateItem {
+        name: item.name.clone(),
+        sub_tasks,
+    }
+}
+
+// ─────────────────────────── Apply Template (used by add_project) ───────────────────────────
+
+/// Create the template's categories and checklist items for a freshly inserted project.
+/// Runs inside the caller's transaction so a failed copy leaves no half-built project.
+pub(crate) async fn apply_template(
+    tx: &mut Transaction<'_, Postgres>,
+    template_id: i32,
+    project_id: i32,
+) -> Result<()> {
+    let exists = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM project_templates WHERE id = $1) AS "v!""#,
+        template_id
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    if !exists {
+        return Err(AppError::NotFound(format!("Template id={} not found", template_id)));
+    }
+
+    let categories = sqlx::query!(
+        "SELECT id, name FROM template_categories WHERE template_id = $1 ORDER BY sort_order, id",
+        template_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    let items = sqlx::query_as!(
+        TreeRow,
+        r#"SELECT i.id, i.template_category_id AS "group_id!", i.parent_id, i.name, i.sort_order
+           FROM template_items i
+           JOIN template_categories c ON c.id = i.template_category_id
+           WHERE c.template_id = $1
+           ORDER BY i.sort_order, i.id"#,
+        template_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    let mut cat_map: HashMap<i32, i32> = HashMap::new();
+    for (order, cat) in categories.iter().enumerate() {
+        let cat_id = sqlx::query_scalar!(
+            "INSERT INTO categories (project_id, name, sort_order) VALUES ($1, $2, $3) RETURNING id",
+            project_id, cat.name, order as i32
+        )
+        .fetch_one(&mut **tx)
+        .await?;
+        cat_map.insert(cat.id, cat_id);
+    }
+
+    let mut item_map: HashMap<i32, i32> = HashMap::new();
+    for item in parents_first(&items, |r| (r.id, r.parent_id)) {
+        let Some(&cat_id) = cat_map.get(&item.group_id) else { continue };
+        let parent_id = item.parent_id.and_then(|p| item_map.get(&p).copied());
+
+        let new_id = sqlx::query_scalar!(
+            "INSERT INTO checklist_items (category_id, parent_id, name, sort_order)
+             VALUES ($1, $2, $3, $4) RETURNING id",
+            cat_id, parent_id, item.name, item.sort_order
+        )
+        .fetch_one(&mut **tx)
+        .await?;
+        item_map.insert(item.id, new_id);
+    }
+
+    Ok(())
+}
+
+// ─────────────────────────── Save Project as Template ───────────────────────────
+
+/// Snapshot a project's categories and item tree as a new named template.
+/// Only the structure is kept — flags, attribution and notes are not copied.
+pub async fn save_project_as_template(
+    pool: &PgPool,
+    project_id: i32,
+    name: &str,
+    description: Option<&str>,
+) -> Result<i32> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::Validation("Numele șablonului nu poate fi gol".into()));
+    }
+
+    let mut tx = pool.begin().await?;
+
+    let project_exists = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM projects WHERE id = $1 AND deleted_at IS NULL) AS "v!""#,
+        project_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if !project_exists {
+        return Err(AppError::NotFound(format!("Project id={} not found", project_id)));
+    }
+
+    let template_id = sqlx::query_scalar!(
+        "INSERT INTO project_templates (name, description) VALUES ($1, $2)
+         ON CONFLICT (name) DO NOTHING
+         RETURNING id",
+        name, description
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::Validation("Există deja un șablon cu acest nume".into()))?;
+
+    let categories = sqlx::query!(
+        "SELECT id, name FROM categories WHERE project_id = $1 ORDER BY sort_order, id",
+        project_id
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let mut cat_map: HashMap<i32, i32> = HashMap::new();
+    for (i, cat) in categories.iter().enumerate() {
+        let tcat_id = sqlx::query_scalar!(
+            "INSERT INTO template_categories (template_id, name, sort_order) VALUES ($1, $2, $3) RETURNING id",
+            template_id, cat.name, i as i32
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        cat_map.insert(cat.id, tcat_id);
+    }
+
+    let items = sqlx::query_as!(
+        TreeRow,
+        r#"SELECT i.id, i.category_id AS "group_id!", i.parent_id, i.name, i.sort_order
+           FROM checklist_items i
+           JOIN categories c ON c.id = i.category_id
+           WHERE c.project_id = $1 AND i.deleted_at IS NULL
+           ORDER BY i.sort_order, i.id"#,
+        project_id
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let mut item_map: HashMap<i32, i32> = HashMap::new();
+    for item in parents_first(&items, |r| (r.id, r.parent_id)) {
+        let Some(&tcat_id) = cat_map.get(&item.group_id) else { continue };
+        let parent_id = item.parent_id.and_then(|p| item_map.get(&p).copied());
+
+        let new_id = sqlx::query_scalar!(
+            "INSERT INTO template_items (template_category_id, parent_id, name, sort_order)
+             VALUES ($1, $2, $3, $4) RETURNING id",
+            tcat_id, parent_id, item.name, item.sort_order
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        item_map.insert(item.id, new_id);
+    }
+
+    tx.commit().await?;
+    Ok(template_id)
+}
@@ -0,0 +1,184 @@
+use sqlx::PgPool;
+
+use crate::errors::{AppError, Result};
+use crate::guard;
+use crate::models::{PurgeReport, TrashEntry};
+
+/// Days a deleted entry stays restorable when no retention is configured.
+pub const DEFAULT_RETENTION_DAYS: i32 = 30;
+
+// ─────────────────────────── List Trash ───────────────────────────
+
+/// Everything in the trash, most recently deleted first. An item deleted together with
+/// its sub-tasks is listed once; restoring it brings the sub-tasks back too.
+pub async fn list_trash(pool: &PgPool) -> Result<Vec<TrashEntry>> {
+    let entries = sqlx::query_as!(
+        TrashEntry,
+        r#"SELECT 'project' AS "kind!", p.id AS "id!", p.title AS "label!",
+                  p.id AS "project_id!", p.title AS "project_title!", p.year AS "year!",
+                  NULL::text AS category, p.deleted_at AS "deleted_at!"
+           FROM projects p
+           WHERE p.deleted_at IS NOT NULL
+           UNION ALL
+           SELECT 'item', i.id, i.name, p.id, p.title, p.year, c.name, i.deleted_at
+           FROM checklist_items i
+           JOIN categories c ON c.id = i.category_id
+           JOIN projects p ON p.id = c.project_id
+           LEFT JOIN checklist_items parent ON parent.id = i.parent_id
+           WHERE i.deleted_at IS NOT NULL
+             AND parent.deleted_at IS DISTINCT FROM i.deleted_at
+           UNION ALL
+           SELECT 'note', n.id, n.text, p.id, p.title, p.year, c.name, n.deleted_at
+           FROM notes n
+           JOIN checklist_items i ON i.id = n.item_id
+           JOIN categories c ON c.id = i.category_id
+           JOIN projects p ON p.id = c.project_id
+           WHERE n.deleted_at IS NOT NULL
+           ORDER BY 8 DESC"#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(entries)
+}
+
+// ─────────────────────────── Restore ───────────────────────────
+
+pub async fn restore_project(pool: &PgPool, project_id: i32) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    guard::ensure_project_writable(&mut tx, project_id).await?;
+
+    let rows = sqlx::query!(
+        "UPDATE projects SET deleted_at = NULL WHERE id = $1 AND deleted_at IS NOT NULL",
+        project_id
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    if rows == 0 {
+        return Err(AppError::NotFound(format!("Deleted project id={} not found", project_id)));
+    }
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Restore an item with the sub-tasks that were deleted together with it.
+/// Sub-tasks deleted separately before stay in the trash.
+pub async fn restore_item(pool: &PgPool, item_id: i32) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    guard::ensure_item_writable(&mut tx, item_id).await?;
+
+    let item = sqlx::query!(
+        r#"SELECT i.deleted_at, parent.deleted_at AS "parent_deleted_at?", p.deleted_at AS project_deleted_at
+           FROM checklist_items i
+           JOIN categories c ON c.id = i.category_id
+           JOIN projects p ON p.id = c.project_id
+           LEFT JOIN checklist_items parent ON parent.id = i.parent_id
+           WHERE i.id = $1"#,
+        item_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .filter(|r| r.deleted_at.is_some())
+    .ok_or_else(|| AppError::NotFound(format!("Deleted checklist item id={} not found", item_id)))?;
+
+    if item.project_deleted_at.is_some() {
+        return Err(AppError::Validation("Restaurați mai întâi proiectul".into()));
+    }
+    if item.parent_deleted_at.is_some() {
+        return Err(AppError::Validation("Restaurați mai întâi elementul părinte".into()));
+    }
+
+    sqlx::query!(
+        "WITH RECURSIVE subtree AS (
+             SELECT id, deleted_at FROM checklist_items WHERE id = $1
+             UNION ALL
+             SELECT c.id, c.deleted_at FROM checklist_items c
+             JOIN subtree s ON c.parent_id = s.id
+             WHERE c.deleted_at = s.deleted_at
+         )
+         UPDATE checklist_items SET deleted_at = NULL WHERE id IN (SELECT id FROM subtree)",
+        item_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+pub async fn restore_note(pool: &PgPool, note_id: i32) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    guard::ensure_note_writable(&mut tx, note_id).await?;
+
+    let note = sqlx::query!(
+        "SELECT n.deleted_at, i.deleted_at AS item_deleted_at FROM notes n
+         JOIN checklist_items i ON i.id = n.item_id
+         WHERE n.id = $1",
+        note_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .filter(|r| r.deleted_at.is_some())
+    .ok_or_else(|| AppError::NotFound(format!("Deleted note id={} not found", note_id)))?;
+
+    if note.item_deleted_at.is_some() {
+        return Err(AppError::Validation("Restaurați mai întâi elementul notei".into()));
+    }
+
+    sqlx::query!("UPDATE notes SET deleted_at = NULL WHERE id = $1", note_id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+// ─────────────────────────── Purge ───────────────────────────
+
+/// Permanently delete trash entries older than `retention_days`
+/// (CASCADE removes what they contain). Archived (read-only) years are left as they are.
+pub async fn purge_trash(pool: &PgPool, retention_days: i32) -> Result<PurgeReport> {
+    if retention_days < 0 {
+        return Err(AppError::Validation("Numărul de zile nu poate fi negativ".into()));
+    }
+
+    let mut tx = pool.begin().await?;
+
+    let notes = sqlx::query!(
+        "DELETE FROM notes n
+         USING checklist_items i, categories c, projects p
+         WHERE i.id = n.item_id AND c.id = i.category_id AND p.id = c.project_id
+           AND n.deleted_at < now() - make_interval(days => $1)
+           AND p.year NOT IN (SELECT year FROM archived_years)",
+        retention_days
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    let items = sqlx::query!(
+        "DELETE FROM checklist_items i
+         USING categories c, projects p
+         WHERE c.id = i.category_id AND p.id = c.project_id
+           AND i.deleted_at < now() - make_interval(days => $1)
+           AND p.year NOT IN (SELECT year FROM archived_years)",
+        retention_days
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    let projects = sqlx::query!(
+        "DELETE FROM projects
+         WHERE deleted_at < now() - make_interval(days => $1)
+           AND year NOT IN (SELECT year FROM archived_years)",
+        retention_days
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    tx.commit().await?;
+    Ok(PurgeReport { projects, items, notes })
+}
@@ -0,0 +1,309 @@
+//! Helpers for checklist trees (parent_id self-references): loading one nested, saving one
+//! over the live items, copying between owners.
+
+use std::collections::{HashMap, HashSet};
+
+use sqlx::{PgPool, Postgres, Transaction};
+
+use crate::errors::Result;
+use crate::models::{ChecklistItem, ChecklistItemNested, NoteNested};
+use crate::projects::build_nested;
+
+/// A category's checklist as nested items with notes (2 queries).
+/// Also returns the flat item count and the note count.
+pub(crate) async fn load_nested_checklist(
+    pool: &PgPool,
+    category_id: i32,
+) -> Result<(Vec<ChecklistItemNested>, i64, i64)> {
+    let items = sqlx::query_as!(
+        ChecklistItem,
+        "SELECT id, category_id, parent_id, name, proposed, verified, sort_order, proposed_by, verified_by,
+                proposed_at, verified_at, reference, needs_review, previous_name, response, document_reference
+         FROM checklist_items WHERE category_id = $1 AND deleted_at IS NULL ORDER BY sort_order, id",
+        category_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let item_ids: Vec<i32> = items.iter().map(|i| i.id).collect();
+    let notes = sqlx::query!(
+        r#"SELECT id, item_id, "user", date, text FROM notes
+           WHERE item_id = ANY($1) AND deleted_at IS NULL ORDER BY id"#,
+        &item_ids
+    )
+    .fetch_all(pool)
+    .await?;
+    let note_count = notes.len() as i64;
+
+    let mut notes_by_item: HashMap<i32, Vec<NoteNested>> = HashMap::new();
+    for n in notes {
+        notes_by_item.entry(n.item_id).or_default().push(NoteNested {
+            id: n.id,
+            user: n.user,
+            date: n.date,
+            text: n.text,
+        });
+    }
+
+    let mut children_by_parent: HashMap<i32, Vec<&ChecklistItem>> = HashMap::new();
+    for item in &items {
+        if let Some(pid) = item.parent_id {
+            children_by_parent.entry(pid).or_default().push(item);
+        }
+    }
+
+    let checklist = items
+        .iter()
+        .filter(|i| i.parent_id.is_none())
+        .map(|i| build_nested(i, &children_by_parent, &notes_by_item))
+        .collect();
+
+    Ok((checklist, items.len() as i64, note_count))
+}
+
+/// Order tree rows depth-first so every parent is visited before its children.
+/// Siblings keep the order they were fetched in. `key` returns `(id, parent_id)`.
+///
+/// Inserting rows in this order lets callers remap `parent_id` to freshly
+/// generated ids with a single pass, at any nesting depth.
+pub(crate) fn parents_first<T>(rows: &[T], key: impl Fn(&T) -> (i32, Option<i32>)) -> Vec<&T> {
+    let mut children: HashMap<i32, Vec<&T>> = HashMap::new();
+    let mut roots = Vec::new();
+    for row in rows {
+        match key(row).1 {
+            Some(pid) => children.entry(pid).or_default().push(row),
+            None => roots.push(row),
+        }
+    }
+
+    let mut ordered = Vec::with_capacity(rows.len());
+    let mut stack: Vec<&T> = roots.into_iter().rev().collect();
+    while let Some(row) = stack.pop() {
+        ordered.push(row);
+        if let Some(kids) = children.get(&key(row).0) {
+            stack.extend(kids.iter().rev());
+        }
+    }
+    ordered
+}
+
+/// Copy the full item trees of the source categories in `cat_map` (source id → target id)
+/// into their target categories. The offer's responses and document references are part
+/// of the requirement and always copied; flags/attribution and notes only on request.
+/// `top_sort_offset` shifts top-level sort_order so copies can be appended after existing items.
+/// Returns the number of items inserted.
+pub(crate) async fn copy_checklist_items(
+    tx: &mut Transaction<'_, Postgres>,
+    cat_map: &HashMap<i32, i32>,
+    keep_flags: bool,
+    keep_notes: bool,
+    top_sort_offset: i32,
+) -> Result<usize> {
+    let source_cat_ids: Vec<i32> = cat_map.keys().copied().collect();
+    let items = sqlx::query_as!(
+        ChecklistItem,
+        "SELECT id, category_id, parent_id, name, proposed, verified, sort_order, proposed_by, verified_by,
+                proposed_at, verified_at, reference, needs_review, previous_name, response, document_reference
+         FROM checklist_items WHERE category_id = ANY($1) AND deleted_at IS NULL
+         ORDER BY sort_order, id",
+        &source_cat_ids
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    let mut item_map: HashMap<i32, i32> = HashMap::new();
+    for item in parents_first(&items, |i| (i.id, i.parent_id)) {
+        let Some(&cat_id) = cat_map.get(&item.category_id) else { continue };
+        let parent_id = item.parent_id.and_then(|p| item_map.get(&p).copied());
+        let sort_order = if item.parent_id.is_none() {
+            item.sort_order + top_sort_offset
+        } else {
+            item.sort_order
+        };
+
+        let new_id = sqlx::query_scalar!(
+            "INSERT INTO checklist_items
+                 (category_id, parent_id, name, proposed, verified, sort_order, proposed_by, verified_by,
+                  proposed_at, verified_at, reference, needs_review, previous_name, response, document_reference)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15) RETURNING id",
+            cat_id,
+            parent_id,
+            item.name,
+            keep_flags && item.proposed,
+            keep_flags && item.verified,
+            sort_order,
+            item.proposed_by.as_ref().filter(|_| keep_flags),
+            item.verified_by.as_ref().filter(|_| keep_flags),
+            item.proposed_at.filter(|_| keep_flags),
+            item.verified_at.filter(|_| keep_flags),
+            item.reference,
+            keep_flags && item.needs_review,
+            item.previous_name.as_ref().filter(|_| keep_flags),
+            item.response,
+            item.document_reference
+        )
+        .fetch_one(&mut **tx)
+        .await?;
+        item_map.insert(item.id, new_id);
+    }
+
+    if keep_notes {
+        let source_item_ids: Vec<i32> = item_map.keys().copied().collect();
+        let notes = sqlx::query!(
+            r#"SELECT item_id, "user", date, text FROM notes
+           WHERE item_id = ANY($1) AND deleted_at IS NULL ORDER BY id"#,
+            &source_item_ids
+        )
+        .fetch_all(&mut **tx)
+        .await?;
+
+        for note in notes {
+            let Some(&item_id) = item_map.get(&note.item_id) else { continue };
+            sqlx::query!(
+                r#"INSERT INTO notes (item_id, "user", date, text) VALUES ($1, $2, $3, $4)"#,
+                item_id, note.user, note.date, note.text
+            )
+            .execute(&mut **tx)
+            .await?;
+        }
+    }
+
+    Ok(item_map.len())
+}
+
+/// Save `items` (any depth) as the live checklist of `category_id`, top-level items at
+/// sort_order 0.. and siblings in their given order. An item or note carrying the id of a
+/// live one of the category is updated in place, the others are inserted; live items and
+/// notes missing from the tree go to the trash (see `trash`), so nothing saved before is
+/// lost. Flag timestamps sent back are kept; newly set flags are stamped now.
+/// Returns the number of items inserted.
+pub(crate) async fn save_checklist_tree(
+    tx: &mut Transaction<'_, Postgres>,
+    category_id: i32,
+    items: &[ChecklistItemNested],
+) -> Result<usize> {
+    let mut live: HashSet<i32> = sqlx::query_scalar!(
+        "SELECT id FROM checklist_items WHERE category_id = $1 AND deleted_at IS NULL FOR UPDATE",
+        category_id
+    )
+    .fetch_all(&mut **tx)
+    .await?
+    .into_iter()
+    .collect();
+    let live_items: Vec<i32> = live.iter().copied().collect();
+    // note id → item id
+    let mut live_notes: HashMap<i32, i32> = sqlx::query!(
+        "SELECT id, item_id FROM notes WHERE item_id = ANY($1) AND deleted_at IS NULL",
+        &live_items
+    )
+    .fetch_all(&mut **tx)
+    .await?
+    .into_iter()
+    .map(|n| (n.id, n.item_id))
+    .collect();
+
+    let mut kept_items = HashSet::new();
+    let mut inserted = 0;
+    // (parent id, sort order, item) — popped in document order
+    let mut stack: Vec<(Option<i32>, i32, &ChecklistItemNested)> = items
+        .iter()
+        .enumerate()
+        .rev()
+        .map(|(i, item)| (None, i as i32, item))
+        .collect();
+
+    while let Some((parent_id, sort_order, item)) = stack.pop() {
+        // An id seen twice is kept once; the copy becomes a new item
+        let id = if live.remove(&item.id) {
+            sqlx::query!(
+                "UPDATE checklist_items
+                 SET parent_id = $2, name = $3, proposed = $4, verified = $5, sort_order = $6,
+                     proposed_by = $7, verified_by = $8,
+                     proposed_at = CASE WHEN $4 THEN COALESCE($9, proposed_at, now()) END,
+                     verified_at = CASE WHEN $5 THEN COALESCE($10, verified_at, now()) END,
+                     reference = $11, needs_review = $12, previous_name = $13,
+                     response = $14, document_reference = $15
+                 WHERE id = $1",
+                item.id, parent_id, item.name, item.proposed, item.verified, sort_order, item.proposed_by,
+                item.verified_by, item.proposed_at, item.verified_at, item.reference, item.needs_review,
+                item.previous_name, item.response, item.document_reference
+            )
+            .execute(&mut **tx)
+            .await?;
+            kept_items.insert(item.id);
+            item.id
+        } else {
+            inserted += 1;
+            insert_item(tx, category_id, parent_id, sort_order, item).await?
+        };
+
+        for note in &item.notes {
+            if live_notes.get(&note.id) == Some(&id) {
+                live_notes.remove(&note.id);
+                sqlx::query!(
+                    r#"UPDATE notes SET "user" = $2, date = $3, text = $4 WHERE id = $1"#,
+                    note.id, note.user, note.date, note.text
+                )
+                .execute(&mut **tx)
+                .await?;
+            } else {
+                insert_note(tx, id, note).await?;
+            }
+        }
+
+        stack.extend(item.sub_tasks.iter().enumerate().rev().map(|(j, sub)| (Some(id), j as i32, sub)));
+    }
+
+    // What is left was removed. Items go to the trash with their sub-tasks (same
+    // deleted_at, restored together) and keep their notes; notes removed from kept
+    // items go on their own.
+    let removed_items: Vec<i32> = live.into_iter().collect();
+    let removed_notes: Vec<i32> = live_notes
+        .into_iter()
+        .filter(|(_, item_id)| kept_items.contains(item_id))
+        .map(|(note_id, _)| note_id)
+        .collect();
+    sqlx::query!("UPDATE checklist_items SET deleted_at = now() WHERE id = ANY($1)", &removed_items)
+        .execute(&mut **tx)
+        .await?;
+    sqlx::query!("UPDATE notes SET deleted_at = now() WHERE id = ANY($1)", &removed_notes)
+        .execute(&mut **tx)
+        .await?;
+
+    Ok(inserted)
+}
+
+/// One item row (not its notes or sub-tasks); returns its id.
+async fn insert_item(
+    tx: &mut Transaction<'_, Postgres>,
+    category_id: i32,
+    parent_id: Option<i32>,
+    sort_order: i32,
+    item: &ChecklistItemNested,
+) -> Result<i32> {
+    let id = sqlx::query_scalar!(
+        "INSERT INTO checklist_items (category_id, parent_id, name, proposed, verified, sort_order, proposed_by, verified_by,
+                                      proposed_at, verified_at, reference, needs_review, previous_name,
+                                      response, document_reference)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8,
+                 CASE WHEN $4 THEN COALESCE($9, now()) END, CASE WHEN $5 THEN COALESCE($10, now()) END,
+                 $11, $12, $13, $14, $15)
+         RETURNING id",
+        category_id, parent_id, item.name, item.proposed, item.verified, sort_order, item.proposed_by,
+        item.verified_by, item.proposed_at, item.verified_at, item.reference, item.needs_review,
+        item.previous_name, item.response, item.document_reference
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+    Ok(id)
+}
+
+async fn insert_note(tx: &mut Transaction<'_, Postgres>, item_id: i32, note: &NoteNested) -> Result<()> {
+    sqlx::query!(
+        r#"INSERT INTO notes (item_id, "user", date, text) VALUES ($1, $2, $3, $4)"#,
+        item_id, note.user, note.date, note.text
+    )
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,79 @@
+//! Parsing of the non-xlsx requirement documents (see `excel/source.rs`), on the small
+//! files in `tests/fixtures`.
+
+use verivia_core::excel::{list_sheets, parse_excel_report};
+use verivia_core::models::{ChecklistItemNested, ImportProfile, IssueKind};
+
+fn fixture(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+/// Header on row 1, "Nr." in column A, requirement in B, response in C.
+fn profile() -> ImportProfile {
+    ImportProfile {
+        header_row: Some(1),
+        name_column: 2,
+        reference_column: Some(1),
+        response_column: Some(3),
+        ..ImportProfile::default()
+    }
+}
+
+fn names(items: &[ChecklistItemNested]) -> Vec<&str> {
+    items.iter().map(|i| i.name.as_str()).collect()
+}
+
+#[test]
+fn csv_in_windows_1250_with_semicolons() {
+    let parsed = parse_excel_report(&fixture("cerinte_cp1250.csv"), &profile()).unwrap();
+
+    // Commas inside the text do not split the columns
+    assert_eq!(
+        names(&parsed.checklist),
+        ["Alimentare 230 V, 50 Hz", "Garanţie minimă 36 luni, on-site"]
+    );
+    assert_eq!(names(&parsed.checklist[0].sub_tasks), ["Protecţie la supratensiune, clasa II"]);
+    assert_eq!(parsed.checklist[0].response.as_deref(), Some("Conform"));
+    assert_eq!(parsed.checklist[1].response, None);
+    assert_eq!(parsed.report.sheet_name, "cerinte_cp1250");
+    assert_eq!(parsed.report.rows_imported, 3);
+}
+
+#[test]
+fn ods_sheets_by_name() {
+    let sheets = list_sheets(&fixture("cerinte.ods")).unwrap();
+    let listed: Vec<_> = sheets.iter().map(|s| (s.name.as_str(), s.index, s.rows)).collect();
+    assert_eq!(listed, [("Generale", 1, 2), ("Tehnic", 2, 5)]);
+
+    let profile = ImportProfile { sheet_name: Some("Tehnic".into()), response_column: None, ..profile() };
+    let parsed = parse_excel_report(&fixture("cerinte.ods"), &profile).unwrap();
+
+    assert_eq!(names(&parsed.checklist), ["Server", "Licențe"]);
+    assert_eq!(names(&parsed.checklist[0].sub_tasks), ["Procesor minim 16 nuclee", "Memorie 128 GB"]);
+    assert_eq!(parsed.checklist[0].sub_tasks[1].reference.as_deref(), Some("1.2"));
+}
+
+#[test]
+fn docx_grid_span_keeps_the_columns() {
+    let sheets = list_sheets(&fixture("cerinte.docx")).unwrap();
+    assert_eq!(sheets.iter().map(|s| s.name.as_str()).collect::<Vec<_>>(), ["Tabel 1"]);
+
+    let parsed = parse_excel_report(&fixture("cerinte.docx"), &profile()).unwrap();
+
+    // The section title spans "Nr." and "Cerință"; its response stays in the third column
+    assert_eq!(names(&parsed.checklist), ["Secţiunea A – Echipamente"]);
+    let section = &parsed.checklist[0];
+    assert_eq!(section.reference, None);
+    assert_eq!(section.response.as_deref(), Some("Conform"));
+    assert_eq!(names(&section.sub_tasks), ["Laptop 15 inch", "Monitor 27 inch"]);
+    assert_eq!(section.sub_tasks[0].response.as_deref(), Some("Conform – pag. 4"));
+
+    let merged: Vec<_> = parsed
+        .report
+        .issues
+        .iter()
+        .filter(|i| i.kind == IssueKind::MergedCell)
+        .map(|i| (i.row, i.column))
+        .collect();
+    assert_eq!(merged, [(2, Some(2))]);
+}
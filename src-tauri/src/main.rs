@@ -3,9 +3,12 @@
     windows_subsystem = "windows"
 )]
 
+use std::collections::HashSet;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::RecvTimeoutError;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use notify::{Config, RecursiveMode, Watcher};
 use regex::Regex;
@@ -15,7 +18,11 @@ use verivia_core::PgPool;
 use tauri::api::dialog::blocking::FileDialogBuilder;
 use tauri::{AppHandle, Manager, State};
 
-use verivia_core::models::{ChecklistItemNested, RoleFlags};
+use verivia_core::models::{
+    ArchiveMode, ChecklistImportOptions, ChecklistItemNested, CloneOptions, DeadlineAlert,
+    ImportProfile, LayoutDetection, LinkedExcel, ParsedChecklist, ProjectDeadlines, ProjectFilter,
+    ProjectMetadata, ProjectQuery, RoleFlags, SheetMapping, SpecChange,
+};
 
 // ═══════════════════════════════════════════════════════════════
 //  Tauri-managed state
@@ -27,6 +34,9 @@ struct AppState {
 
 type SharedWatcher = Arc<Mutex<Option<notify::RecommendedWatcher>>>;
 
+/// Watcher of the spreadsheets linked to the current year's categories.
+struct ExcelWatcher(Mutex<Option<notify::RecommendedWatcher>>);
+
 // ═══════════════════════════════════════════════════════════════
 //  Desktop-only helpers (config.json, file dialogs)
 // ═══════════════════════════════════════════════════════════════
@@ -128,9 +138,9 @@ async fn sync_projects_once(pool: &PgPool, year: &str) -> Result<(), String> {
         .await
         .map_err(|e| e.to_string())?;
 
-    let mut by_title: HashMap<String, (i32, String)> = HashMap::new();
+    let mut by_title = HashMap::new();
     for p in &existing {
-        by_title.insert(p.title.to_lowercase(), (p.id, p.date.clone()));
+        by_title.insert(p.title.to_lowercase(), (p.id, p.date));
     }
 
     let entries = fs::read_dir(&dir).map_err(|e| e.to_string())?;
@@ -140,14 +150,16 @@ async fn sync_projects_once(pool: &PgPool, year: &str) -> Result<(), String> {
 
         let folder_name = path.file_name().unwrap().to_string_lossy();
         let Some((date_part, title_part)) = parse_folder_name(&folder_name) else { continue };
+        // Folder names like "13.45 - ..." are not valid dates — skip rather than abort the sync
+        let Ok(date) = verivia_core::models::date_from_month_day(&date_part, year) else { continue };
 
         match by_title.get(&title_part.to_lowercase()) {
-            Some(&(id, ref old_date)) if *old_date != date_part => {
-                verivia_core::projects::edit_project(pool, id, &title_part, &format!("{}.{}", date_part, year))
+            Some(&(id, old_date)) if old_date != date => {
+                verivia_core::projects::edit_project(pool, id, &title_part, date)
                     .await.map_err(|e| e.to_string())?;
             }
             None => {
-                let proj = verivia_core::projects::add_project(pool, &title_part, &format!("{}.{}", date_part, year), year)
+                let proj = verivia_core::projects::add_project(pool, &title_part, date, year, None)
                     .await.map_err(|e| e.to_string())?;
                 verivia_core::projects::save_project_folder(pool, proj.id, &path.to_string_lossy())
                     .await.map_err(|e| e.to_string())?;
@@ -192,6 +204,205 @@ fn spawn_dir_watcher(
     Ok(Some(watcher))
 }
 
+// ═══════════════════════════════════════════════════════════════
+//  Linked Excel watcher (desktop-only)
+// ═══════════════════════════════════════════════════════════════
+
+/// Quiet time after the last event of a linked file before it is checked: Excel
+/// saves through a temporary file, several writes and a rename.
+const EXCEL_SETTLE: Duration = Duration::from_secs(2);
+
+/// (Re)start watching the year's linked spreadsheets. With `check_now`, files changed
+/// while nothing was watching them (app closed, other year) are reported right away.
+async fn start_excel_watcher(app: AppHandle, pool: PgPool, year: String, check_now: bool) {
+    let linked = match verivia_core::linked_excel::linked_files(&pool, &year).await {
+        Ok(linked) => linked,
+        Err(e) => {
+            eprintln!("Excel watcher error: {}", e);
+            return;
+        }
+    };
+
+    {
+        let state = app.state::<ExcelWatcher>();
+        let mut watcher = state.0.lock().unwrap();
+        // Dropping the previous watcher also ends its thread
+        *watcher = None;
+        match spawn_excel_watcher(app.clone(), pool.clone(), year.clone(), &linked) {
+            Ok(w) => *watcher = w,
+            Err(e) => eprintln!("Excel watcher error: {}", e),
+        }
+    }
+
+    if check_now {
+        match verivia_core::linked_excel::check_year(&pool, &year).await {
+            Ok(changes) => notify_excel_changes(&app, &changes),
+            Err(e) => eprintln!("Excel check error: {}", e),
+        }
+    }
+}
+
+/// Watch the current year again after a file was linked or re-imported.
+async fn refresh_excel_watcher(app: &AppHandle, pool: &PgPool) {
+    let year = app.state::<AppState>().current_year.lock().unwrap().clone();
+    start_excel_watcher(app.clone(), pool.clone(), year, false).await;
+}
+
+fn spawn_excel_watcher(
+    app_handle: AppHandle,
+    pool: PgPool,
+    year: String,
+    linked: &[LinkedExcel],
+) -> notify::Result<Option<notify::RecommendedWatcher>> {
+    // Excel replaces the file on save, so the folders are watched, not the files
+    let files: Vec<&Path> = linked.iter().map(|l| Path::new(&l.excel_path)).collect();
+    let mut dirs: Vec<&Path> = files.iter().filter_map(|f| f.parent()).filter(|d| d.is_dir()).collect();
+    dirs.sort();
+    dirs.dedup();
+    if dirs.is_empty() {
+        return Ok(None);
+    }
+    let names: HashSet<_> = files.iter().filter_map(|f| f.file_name()).map(|n| n.to_os_string()).collect();
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    watcher.configure(Config::default().with_poll_interval(Duration::from_secs(2)))?;
+    for dir in dirs {
+        watcher.watch(dir, RecursiveMode::NonRecursive)?;
+    }
+
+    std::thread::spawn(move || {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        // Linked files with events since their last check
+        let mut pending: HashSet<PathBuf> = HashSet::new();
+        loop {
+            let next = if pending.is_empty() {
+                rx.recv().map_err(|_| RecvTimeoutError::Disconnected)
+            } else {
+                rx.recv_timeout(EXCEL_SETTLE)
+            };
+            match next {
+                Ok(Ok(event)) => pending.extend(
+                    event.paths.into_iter().filter(|p| p.file_name().is_some_and(|n| names.contains(n))),
+                ),
+                Ok(Err(_)) => {}
+                Err(RecvTimeoutError::Timeout) => {
+                    for path in pending.drain() {
+                        let path = path.to_string_lossy();
+                        match rt.block_on(verivia_core::linked_excel::check_file(&pool, &year, &path)) {
+                            Ok(changes) => notify_excel_changes(&app_handle, &changes),
+                            Err(e) => eprintln!("Excel check error: {}", e),
+                        }
+                    }
+                }
+                // The watcher was replaced or dropped
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+    });
+
+    Ok(Some(watcher))
+}
+
+/// Emit `excel_changed` (with the diffs) and show a desktop notification per category.
+fn notify_excel_changes(app: &AppHandle, changes: &[SpecChange]) {
+    if changes.is_empty() {
+        return;
+    }
+    let _ = app.emit_all("excel_changed", changes);
+
+    let identifier = app.config().tauri.bundle.identifier.clone();
+    for change in changes {
+        let file = Path::new(&change.excel_path)
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| change.excel_path.clone());
+        let body = format!(
+            "{} s-a modificat de la ultimul import ({}): {} cerinte noi, {} modificate, {} eliminate",
+            file, change.category_name, change.diff.added, change.diff.renamed, change.diff.removed
+        );
+        if let Err(e) = tauri::api::notification::Notification::new(&identifier)
+            .title(&change.project_title)
+            .body(body)
+            .show()
+        {
+            eprintln!("Notification error: {}", e);
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════
+//  Deadline reminders (desktop-only)
+// ═══════════════════════════════════════════════════════════════
+
+/// Submission deadlines closer than this trigger a desktop notification.
+const DEADLINE_REMINDER_DAYS: i32 = 3;
+
+/// Check deadlines on startup, then once a day, for as long as the app runs.
+fn spawn_deadline_reminders(app_handle: AppHandle, pool: PgPool) {
+    std::thread::spawn(move || {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        loop {
+            match rt.block_on(verivia_core::deadlines::projects_due_within(&pool, DEADLINE_REMINDER_DAYS)) {
+                Ok(alerts) => notify_deadlines(&app_handle, &alerts),
+                Err(e) => eprintln!("Deadline reminder error: {}", e),
+            }
+            std::thread::sleep(std::time::Duration::from_secs(24 * 60 * 60));
+        }
+    });
+}
+
+fn notify_deadlines(app: &AppHandle, alerts: &[DeadlineAlert]) {
+    if alerts.is_empty() {
+        return;
+    }
+    let _ = app.emit_all("deadline_alerts", alerts);
+
+    let identifier = app.config().tauri.bundle.identifier.clone();
+    for alert in alerts {
+        let deadline = alert.submission_deadline.with_timezone(&chrono::Local);
+        let body = format!(
+            "Termen de depunere: {}\n{} elemente neverificate ({})",
+            deadline.format("%d.%m.%Y %H:%M"),
+            alert.unverified_items,
+            alert.incomplete_categories.join(", ")
+        );
+        if let Err(e) = tauri::api::notification::Notification::new(&identifier)
+            .title(&alert.title)
+            .body(body)
+            .show()
+        {
+            eprintln!("Notification error: {}", e);
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════
+//  Trash purge (desktop-only)
+// ═══════════════════════════════════════════════════════════════
+
+/// Days deleted entries stay restorable — `trash_retention_days` in the local config.
+fn trash_retention_days() -> i32 {
+    load_cfg()
+        .get("trash_retention_days")
+        .and_then(Value::as_i64)
+        .map(|d| d as i32)
+        .unwrap_or(verivia_core::trash::DEFAULT_RETENTION_DAYS)
+}
+
+/// Purge expired trash on startup, then once a day.
+fn spawn_trash_purge(pool: PgPool) {
+    std::thread::spawn(move || {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        loop {
+            if let Err(e) = rt.block_on(verivia_core::trash::purge_trash(&pool, trash_retention_days())) {
+                eprintln!("Trash purge error: {}", e);
+            }
+            std::thread::sleep(std::time::Duration::from_secs(24 * 60 * 60));
+        }
+    });
+}
+
 // ═══════════════════════════════════════════════════════════════
 //  Tauri commands — thin wrappers around verivia_core
 // ═══════════════════════════════════════════════════════════════
@@ -227,9 +438,12 @@ async fn auth_register(
 async fn load_projects(
     pool: State<'_, PgPool>,
     state: State<'_, AppState>,
+    filter: Option<ProjectFilter>,
 ) -> Result<Value, String> {
     let year = state.current_year.lock().unwrap().clone();
-    let projects = verivia_core::projects::list_projects(&pool, &year)
+    let filter = filter.unwrap_or_default();
+    // Counters only — checklists are fetched per category with `load_category`
+    let projects = verivia_core::project_query::list_project_summaries(&pool, &year, &filter)
         .await
         .map_err(|e| e.to_string())?;
 
@@ -237,20 +451,74 @@ async fn load_projects(
     serde_json::to_value(json!({ "projects": projects })).map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+async fn load_category(
+    pool: State<'_, PgPool>,
+    project_id: i32,
+    category_name: String,
+) -> Result<Value, String> {
+    let category = verivia_core::checklist::load_category(&pool, project_id, &category_name)
+        .await
+        .map_err(|e| e.to_string())?;
+    serde_json::to_value(category).map_err(|e| e.to_string())
+}
+
+/// Paged, server-side filtered project list. With `query.summary` each category
+/// carries progress counters instead of its checklist.
+#[tauri::command]
+async fn query_projects(pool: State<'_, PgPool>, query: ProjectQuery) -> Result<Value, String> {
+    let page = if query.summary {
+        serde_json::to_value(
+            verivia_core::project_query::query_project_summaries(&pool, &query)
+                .await
+                .map_err(|e| e.to_string())?,
+        )
+    } else {
+        serde_json::to_value(
+            verivia_core::project_query::query_projects(&pool, &query)
+                .await
+                .map_err(|e| e.to_string())?,
+        )
+    };
+    page.map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 async fn add_project(
     pool: State<'_, PgPool>,
     state: State<'_, AppState>,
     title: String,
     date: String,
+    template_id: Option<i32>,
 ) -> Result<(), String> {
     let year = state.current_year.lock().unwrap().clone();
-    verivia_core::projects::add_project(&pool, &title, &date, &year)
+    let date = verivia_core::models::parse_legacy_date(&date).map_err(|e| e.to_string())?;
+    verivia_core::projects::add_project(&pool, &title, date, &year, template_id)
         .await
         .map_err(|e| e.to_string())?;
     Ok(())
 }
 
+#[tauri::command]
+async fn list_project_templates(pool: State<'_, PgPool>) -> Result<Value, String> {
+    let templates = verivia_core::templates::list_project_templates(&pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    serde_json::to_value(templates).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn save_project_as_template(
+    pool: State<'_, PgPool>,
+    project_id: i32,
+    name: String,
+    description: Option<String>,
+) -> Result<i32, String> {
+    verivia_core::templates::save_project_as_template(&pool, project_id, &name, description.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 async fn edit_project(
     pool: State<'_, PgPool>,
@@ -258,11 +526,213 @@ async fn edit_project(
     new_title: String,
     new_date: String,
 ) -> Result<(), String> {
-    verivia_core::projects::edit_project(&pool, id, &new_title, &new_date)
+    let new_date = verivia_core::models::parse_legacy_date(&new_date).map_err(|e| e.to_string())?;
+    verivia_core::projects::edit_project(&pool, id, &new_title, new_date)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn set_project_metadata(
+    pool: State<'_, PgPool>,
+    project_id: i32,
+    metadata: ProjectMetadata,
+) -> Result<(), String> {
+    verivia_core::projects::set_project_metadata(&pool, project_id, &metadata)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn list_procedure_types() -> Vec<&'static str> {
+    verivia_core::projects::PROCEDURE_TYPES.to_vec()
+}
+
+#[tauri::command]
+async fn search(
+    pool: State<'_, PgPool>,
+    text: String,
+    year: Option<String>,
+    limit: Option<i64>,
+) -> Result<Value, String> {
+    let hits = verivia_core::search::search(&pool, &text, year.as_deref(), limit)
+        .await
+        .map_err(|e| e.to_string())?;
+    serde_json::to_value(hits).map_err(|e| e.to_string())
+}
+
+/// Dashboard aggregates for `year` (defaults to the current year).
+#[tauri::command]
+async fn get_year_stats(
+    pool: State<'_, PgPool>,
+    state: State<'_, AppState>,
+    year: Option<String>,
+) -> Result<Value, String> {
+    let year = year.unwrap_or_else(|| state.current_year.lock().unwrap().clone());
+    let stats = verivia_core::stats::year_stats(&pool, &year)
+        .await
+        .map_err(|e| e.to_string())?;
+    serde_json::to_value(stats).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn get_project_stats(pool: State<'_, PgPool>, project_id: i32) -> Result<Value, String> {
+    let stats = verivia_core::stats::project_stats(&pool, project_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    serde_json::to_value(stats).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn list_project_statuses() -> Vec<&'static str> {
+    verivia_core::status::PROJECT_STATUSES.to_vec()
+}
+
+/// The change is recorded under the caller's login token, not a name sent from the frontend.
+#[tauri::command]
+async fn set_project_status(
+    pool: State<'_, PgPool>,
+    token: String,
+    project_id: i32,
+    status: String,
+    override_reason: Option<String>,
+) -> Result<(), String> {
+    let claims = verivia_core::auth::verify_token(&token).map_err(|e| e.to_string())?;
+    verivia_core::status::set_project_status(
+        &pool,
+        project_id,
+        &status,
+        &claims.email,
+        override_reason.as_deref(),
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn project_status_history(pool: State<'_, PgPool>, project_id: i32) -> Result<Value, String> {
+    let history = verivia_core::status::status_history(&pool, project_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    serde_json::to_value(history).map_err(|e| e.to_string())
+}
+
+/// Lock a project's checklist, or only one category when `category_name` is given.
+/// The lock is recorded under the caller's login token, as for `unlock_checklist`.
+#[tauri::command]
+async fn lock_checklist(
+    pool: State<'_, PgPool>,
+    token: String,
+    project_id: i32,
+    category_name: Option<String>,
+    reason: Option<String>,
+) -> Result<(), String> {
+    let claims = verivia_core::auth::verify_token(&token).map_err(|e| e.to_string())?;
+    match category_name {
+        Some(name) => {
+            let cat_id = verivia_core::checklist::get_category_id(&pool, project_id, &name)
+                .await
+                .map_err(|e| e.to_string())?;
+            verivia_core::locks::lock_category(&pool, cat_id, &claims.email, reason.as_deref()).await
+        }
+        None => verivia_core::locks::lock_project(&pool, project_id, &claims.email, reason.as_deref()).await,
+    }
+    .map_err(|e| e.to_string())
+}
+
+/// Admins only — the caller is identified by their login token, not by a name
+/// sent from the frontend.
+#[tauri::command]
+async fn unlock_checklist(
+    pool: State<'_, PgPool>,
+    token: String,
+    project_id: i32,
+    category_name: Option<String>,
+    reason: String,
+) -> Result<(), String> {
+    let claims = verivia_core::auth::verify_token(&token).map_err(|e| e.to_string())?;
+    match category_name {
+        Some(name) => {
+            let cat_id = verivia_core::checklist::get_category_id(&pool, project_id, &name)
+                .await
+                .map_err(|e| e.to_string())?;
+            verivia_core::locks::unlock_category(&pool, cat_id, claims.sub, &reason).await
+        }
+        None => verivia_core::locks::unlock_project(&pool, project_id, claims.sub, &reason).await,
+    }
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn get_lock_state(pool: State<'_, PgPool>, project_id: i32) -> Result<Value, String> {
+    let state = verivia_core::locks::lock_state(&pool, project_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    serde_json::to_value(state).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn lock_history(pool: State<'_, PgPool>, project_id: i32) -> Result<Value, String> {
+    let history = verivia_core::locks::lock_history(&pool, project_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    serde_json::to_value(history).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn set_project_deadlines(
+    pool: State<'_, PgPool>,
+    project_id: i32,
+    deadlines: ProjectDeadlines,
+) -> Result<(), String> {
+    verivia_core::deadlines::set_project_deadlines(&pool, project_id, &deadlines)
         .await
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+async fn list_upcoming_deadlines(pool: State<'_, PgPool>, days: i32) -> Result<Value, String> {
+    let alerts = verivia_core::deadlines::projects_due_within(&pool, days)
+        .await
+        .map_err(|e| e.to_string())?;
+    serde_json::to_value(alerts).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn list_overdue_projects(pool: State<'_, PgPool>) -> Result<Value, String> {
+    let alerts = verivia_core::deadlines::overdue_projects(&pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    serde_json::to_value(alerts).map_err(|e| e.to_string())
+}
+
+/// Projects whose old text date could not be converted (see migration 010).
+#[tauri::command]
+async fn list_project_date_issues(pool: State<'_, PgPool>) -> Result<Value, String> {
+    let issues = verivia_core::projects::list_date_issues(&pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    serde_json::to_value(issues).map_err(|e| e.to_string())
+}
+
+/// The clone goes into the current year, whatever the source's year.
+#[tauri::command]
+async fn clone_project(
+    pool: State<'_, PgPool>,
+    state: State<'_, AppState>,
+    id: i32,
+    new_title: String,
+    new_date: String,
+    options: CloneOptions,
+) -> Result<Value, String> {
+    let year = state.current_year.lock().unwrap().clone();
+    let new_date = verivia_core::models::parse_legacy_date(&new_date).map_err(|e| e.to_string())?;
+    let project = verivia_core::projects::clone_project(&pool, id, &new_title, new_date, &year, &options)
+        .await
+        .map_err(|e| e.to_string())?;
+    serde_json::to_value(project).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 async fn delete_project(pool: State<'_, PgPool>, id: i32) -> Result<(), String> {
     // Guard: refuse to delete while the project's folder still exists on disk.
@@ -284,28 +754,291 @@ async fn delete_project(pool: State<'_, PgPool>, id: i32) -> Result<(), String>
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+async fn list_trash(pool: State<'_, PgPool>) -> Result<Value, String> {
+    let entries = verivia_core::trash::list_trash(&pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    serde_json::to_value(entries).map_err(|e| e.to_string())
+}
+
+/// Move a checklist item (with its sub-tasks) or a note to the trash; `kind` is "item"
+/// or "note". Projects go through `delete_project`.
+#[tauri::command]
+async fn move_to_trash(pool: State<'_, PgPool>, kind: String, id: i32) -> Result<(), String> {
+    match kind.as_str() {
+        "item" => verivia_core::checklist::delete_item(&pool, id).await,
+        "note" => verivia_core::notes::delete_note(&pool, id).await,
+        _ => return Err(format!("Unknown trash entry kind: {}", kind)),
+    }
+    .map_err(|e| e.to_string())
+}
+
+/// Restore a trash entry; `kind` is "project", "item" or "note" as in `list_trash`.
+#[tauri::command]
+async fn restore_from_trash(pool: State<'_, PgPool>, kind: String, id: i32) -> Result<(), String> {
+    match kind.as_str() {
+        "project" => verivia_core::trash::restore_project(&pool, id).await,
+        "item" => verivia_core::trash::restore_item(&pool, id).await,
+        "note" => verivia_core::trash::restore_note(&pool, id).await,
+        _ => return Err(format!("Unknown trash entry kind: {}", kind)),
+    }
+    .map_err(|e| e.to_string())
+}
+
+/// Permanently delete trash entries older than the configured retention.
+#[tauri::command]
+async fn purge_trash(pool: State<'_, PgPool>) -> Result<Value, String> {
+    let report = verivia_core::trash::purge_trash(&pool, trash_retention_days())
+        .await
+        .map_err(|e| e.to_string())?;
+    serde_json::to_value(report).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn get_trash_retention() -> i32 {
+    trash_retention_days()
+}
+
+#[tauri::command]
+fn set_trash_retention(days: i32) -> Result<(), String> {
+    if days < 0 {
+        return Err("Numărul de zile nu poate fi negativ".into());
+    }
+    let mut cfg = load_cfg();
+    cfg["trash_retention_days"] = json!(days);
+    save_cfg(&cfg);
+    Ok(())
+}
+
+/// Archive a year to a compressed file chosen by the user (suggested next to the
+/// year's projects folder). Returns `null` if the dialog was cancelled.
+#[tauri::command]
+async fn archive_year(pool: State<'_, PgPool>, year: String, mode: ArchiveMode) -> Result<Value, String> {
+    let mut dialog = FileDialogBuilder::new()
+        .set_title("Salvează arhiva anului")
+        .set_file_name(&format!("verivia-{}.json.gz", year));
+    if let Some(dir) = get_projects_dir_silent(&year) {
+        dialog = dialog.set_directory(dir);
+    }
+    let Some(path) = dialog.save_file() else {
+        return Ok(Value::Null);
+    };
+
+    let report = verivia_core::archive::archive_year(&pool, &year, &path, mode)
+        .await
+        .map_err(|e| e.to_string())?;
+    serde_json::to_value(report).map_err(|e| e.to_string())
+}
+
+/// Restore a year from an archive file picked by the user. Returns `null` if cancelled.
+#[tauri::command]
+async fn restore_year_archive(pool: State<'_, PgPool>) -> Result<Value, String> {
+    let Some(path) = pick_path("Alege arhiva de restaurat", false) else {
+        return Ok(Value::Null);
+    };
+    let report = verivia_core::archive::restore_year(&pool, &path)
+        .await
+        .map_err(|e| e.to_string())?;
+    serde_json::to_value(report).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn list_archived_years(pool: State<'_, PgPool>) -> Result<Value, String> {
+    let years = verivia_core::archive::list_archived_years(&pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    serde_json::to_value(years).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 async fn save_checklist(
     pool: State<'_, PgPool>,
     project_id: i32,
     category_name: String,
     items: Vec<ChecklistItemNested>,
+    excel_hash: Option<String>,
 ) -> Result<(), String> {
     let cat_id = verivia_core::checklist::get_category_id(&pool, project_id, &category_name)
         .await
         .map_err(|e| e.to_string())?;
-    verivia_core::checklist::save_category_checklist(&pool, cat_id, &items)
+    verivia_core::checklist::save_category_checklist(&pool, cat_id, &items, excel_hash.as_deref())
         .await
         .map_err(|e| e.to_string())
 }
 
+/// Set our response to one requirement (item id as in `load_category`); blank clears it.
+#[tauri::command]
+async fn set_item_response(
+    pool: State<'_, PgPool>,
+    item_id: i32,
+    response: Option<String>,
+    document_reference: Option<String>,
+) -> Result<(), String> {
+    verivia_core::checklist::set_item_response(&pool, item_id, response.as_deref(), document_reference.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Attach an Excel file to a category. `category_name` defaults to Tehnic,
+/// which is what the current Excel import dialog uses.
 #[tauri::command]
 async fn save_excel_path(
+    app: AppHandle,
     pool: State<'_, PgPool>,
     project_id: i32,
     path: String,
+    category_name: Option<String>,
 ) -> Result<(), String> {
-    verivia_core::checklist::save_excel_path(&pool, project_id, &path)
+    let category_name = category_name.unwrap_or_else(|| "Tehnic".to_string());
+    let cat_id = verivia_core::checklist::get_category_id(&pool, project_id, &category_name)
+        .await
+        .map_err(|e| e.to_string())?;
+    verivia_core::checklist::save_excel_path(&pool, cat_id, &path)
+        .await
+        .map_err(|e| e.to_string())?;
+    refresh_excel_watcher(&app, &pool).await;
+    Ok(())
+}
+
+#[tauri::command]
+async fn create_category(
+    pool: State<'_, PgPool>,
+    project_id: i32,
+    name: String,
+) -> Result<Value, String> {
+    let category = verivia_core::categories::create_category(&pool, project_id, &name)
+        .await
+        .map_err(|e| e.to_string())?;
+    serde_json::to_value(category).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn rename_category(
+    pool: State<'_, PgPool>,
+    project_id: i32,
+    category_name: String,
+    new_name: String,
+) -> Result<(), String> {
+    let cat_id = verivia_core::checklist::get_category_id(&pool, project_id, &category_name)
+        .await
+        .map_err(|e| e.to_string())?;
+    verivia_core::categories::rename_category(&pool, cat_id, &new_name)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn delete_category(
+    pool: State<'_, PgPool>,
+    project_id: i32,
+    category_name: String,
+) -> Result<(), String> {
+    let cat_id = verivia_core::checklist::get_category_id(&pool, project_id, &category_name)
+        .await
+        .map_err(|e| e.to_string())?;
+    verivia_core::categories::delete_category(&pool, cat_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn reorder_categories(
+    pool: State<'_, PgPool>,
+    project_id: i32,
+    category_names: Vec<String>,
+) -> Result<(), String> {
+    let mut ids = Vec::with_capacity(category_names.len());
+    for name in &category_names {
+        let id = verivia_core::checklist::get_category_id(&pool, project_id, name)
+            .await
+            .map_err(|e| e.to_string())?;
+        ids.push(id);
+    }
+    verivia_core::categories::reorder_categories(&pool, project_id, &ids)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Projects of any year (id/title/date only), used to pick an import source.
+#[tauri::command]
+async fn list_projects_by_year(pool: State<'_, PgPool>, year: String) -> Result<Value, String> {
+    let projects = verivia_core::projects::list_projects_light(&pool, &year)
+        .await
+        .map_err(|e| e.to_string())?;
+    serde_json::to_value(projects).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn preview_checklist_import(
+    pool: State<'_, PgPool>,
+    source_project_id: i32,
+    source_category_name: String,
+    project_id: i32,
+    category_name: String,
+) -> Result<Value, String> {
+    let source_id = verivia_core::checklist::get_category_id(&pool, source_project_id, &source_category_name)
+        .await
+        .map_err(|e| e.to_string())?;
+    let target_id = verivia_core::checklist::get_category_id(&pool, project_id, &category_name)
+        .await
+        .map_err(|e| e.to_string())?;
+    let preview = verivia_core::categories::preview_checklist_import(&pool, source_id, target_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    serde_json::to_value(preview).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn import_checklist(
+    pool: State<'_, PgPool>,
+    source_project_id: i32,
+    source_category_name: String,
+    project_id: i32,
+    category_name: String,
+    options: ChecklistImportOptions,
+) -> Result<usize, String> {
+    let source_id = verivia_core::checklist::get_category_id(&pool, source_project_id, &source_category_name)
+        .await
+        .map_err(|e| e.to_string())?;
+    let target_id = verivia_core::checklist::get_category_id(&pool, project_id, &category_name)
+        .await
+        .map_err(|e| e.to_string())?;
+    verivia_core::categories::import_checklist(&pool, source_id, target_id, &options)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Admins only, identified by their login token (as for `unlock_checklist`).
+#[tauri::command]
+async fn set_category_role(
+    pool: State<'_, PgPool>,
+    token: String,
+    user_id: i32,
+    category: String,
+    roles: RoleFlags,
+) -> Result<(), String> {
+    let claims = verivia_core::auth::verify_token(&token).map_err(|e| e.to_string())?;
+    verivia_core::auth::require_admin(&pool, claims.sub)
+        .await
+        .map_err(|e| e.to_string())?;
+    verivia_core::auth::set_category_role(&pool, user_id, &category, &roles)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn remove_category_role(
+    pool: State<'_, PgPool>,
+    token: String,
+    user_id: i32,
+    category: String,
+) -> Result<(), String> {
+    let claims = verivia_core::auth::verify_token(&token).map_err(|e| e.to_string())?;
+    verivia_core::auth::require_admin(&pool, claims.sub)
+        .await
+        .map_err(|e| e.to_string())?;
+    verivia_core::auth::remove_category_role(&pool, user_id, &category)
         .await
         .map_err(|e| e.to_string())
 }
@@ -321,11 +1054,233 @@ async fn save_project_folder(
         .map_err(|e| e.to_string())
 }
 
+/// Parse a requirements spreadsheet with the chosen import profile
+/// (layout detected automatically when `profile_id` is omitted).
+#[tauri::command]
+async fn load_technical_data(
+    pool: State<'_, PgPool>,
+    file_path: String,
+    profile_id: Option<i32>,
+) -> Result<Value, String> {
+    let parsed = match profile_id {
+        Some(id) => {
+            let profile = verivia_core::import_profiles::get_import_profile(&pool, id)
+                .await
+                .map_err(|e| e.to_string())?;
+            verivia_core::excel::parse_excel_report(&file_path, &profile)
+        }
+        None => verivia_core::excel::parse_technical_excel_report(&file_path),
+    }
+    .map_err(|e| e.to_string())?;
+    parsed_with_hash(parsed, &file_path)
+}
+
+/// The parsed checklist with the hash of the file it was read from (`excelHash`), which
+/// `save_checklist` records as the import when the checklist is saved.
+fn parsed_with_hash(parsed: ParsedChecklist, file_path: &str) -> Result<Value, String> {
+    let mut value = serde_json::to_value(parsed).map_err(|e| e.to_string())?;
+    value["excelHash"] = json!(verivia_core::linked_excel::file_hash(file_path).ok());
+    Ok(value)
+}
+
+#[tauri::command]
+fn list_excel_sheets(file_path: String) -> Result<Value, String> {
+    let sheets = verivia_core::excel::list_sheets(&file_path).map_err(|e| e.to_string())?;
+    serde_json::to_value(sheets).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn preview_sheet_import(
+    pool: State<'_, PgPool>,
+    project_id: i32,
+    file_path: String,
+    mappings: Vec<SheetMapping>,
+) -> Result<Value, String> {
+    let preview = verivia_core::sheet_import::preview_sheet_import(&pool, project_id, &file_path, &mappings)
+        .await
+        .map_err(|e| e.to_string())?;
+    serde_json::to_value(preview).map_err(|e| e.to_string())
+}
+
+/// Import several sheets into their categories at once (all or nothing).
+#[tauri::command]
+async fn import_excel_sheets(
+    app: AppHandle,
+    pool: State<'_, PgPool>,
+    project_id: i32,
+    file_path: String,
+    mappings: Vec<SheetMapping>,
+) -> Result<Value, String> {
+    let imported = verivia_core::sheet_import::import_sheets(&pool, project_id, &file_path, &mappings)
+        .await
+        .map_err(|e| e.to_string())?;
+    refresh_excel_watcher(&app, &pool).await;
+    serde_json::to_value(imported).map_err(|e| e.to_string())
+}
+
+/// Compare an updated specification with the category's checklist (nothing is written).
+/// Without `profile_id` the file is read like at its last import (sheet and profile).
+#[tauri::command]
+async fn preview_reimport(
+    pool: State<'_, PgPool>,
+    project_id: i32,
+    category_name: String,
+    file_path: String,
+    profile_id: Option<i32>,
+) -> Result<Value, String> {
+    let cat_id = verivia_core::checklist::get_category_id(&pool, project_id, &category_name)
+        .await
+        .map_err(|e| e.to_string())?;
+    let (sheet, profile_id) = import_source(&pool, cat_id, profile_id).await?;
+    let diff = verivia_core::reimport::preview_reimport(&pool, cat_id, &file_path, profile_id, sheet.as_deref())
+        .await
+        .map_err(|e| e.to_string())?;
+    serde_json::to_value(diff).map_err(|e| e.to_string())
+}
+
+/// Re-import keeping flags and notes of the matched requirements.
+#[tauri::command]
+async fn apply_reimport(
+    app: AppHandle,
+    pool: State<'_, PgPool>,
+    project_id: i32,
+    category_name: String,
+    file_path: String,
+    profile_id: Option<i32>,
+) -> Result<Value, String> {
+    let cat_id = verivia_core::checklist::get_category_id(&pool, project_id, &category_name)
+        .await
+        .map_err(|e| e.to_string())?;
+    let (sheet, profile_id) = import_source(&pool, cat_id, profile_id).await?;
+    let diff = verivia_core::reimport::apply_reimport(&pool, cat_id, &file_path, profile_id, sheet.as_deref())
+        .await
+        .map_err(|e| e.to_string())?;
+    refresh_excel_watcher(&app, &pool).await;
+    serde_json::to_value(diff).map_err(|e| e.to_string())
+}
+
+/// The given profile (with its own sheet), else the sheet and profile of the last import.
+async fn import_source(
+    pool: &PgPool,
+    category_id: i32,
+    profile_id: Option<i32>,
+) -> Result<(Option<String>, Option<i32>), String> {
+    match profile_id {
+        Some(id) => Ok((None, Some(id))),
+        None => verivia_core::reimport::last_import_source(pool, category_id)
+            .await
+            .map_err(|e| e.to_string()),
+    }
+}
+
+/// Linked spreadsheets of the current year whose requirements changed since they were
+/// imported, each with the re-import diff (also pushed as `excel_changed` when saved).
+#[tauri::command]
+async fn check_excel_changes(pool: State<'_, PgPool>, state: State<'_, AppState>) -> Result<Value, String> {
+    let year = state.current_year.lock().unwrap().clone();
+    let changes = verivia_core::linked_excel::check_year(&pool, &year)
+        .await
+        .map_err(|e| e.to_string())?;
+    serde_json::to_value(changes).map_err(|e| e.to_string())
+}
+
+/// Keep the checklist as is: the linked file's current content counts as imported.
+#[tauri::command]
+async fn acknowledge_excel_change(
+    pool: State<'_, PgPool>,
+    project_id: i32,
+    category_name: String,
+) -> Result<(), String> {
+    let cat_id = verivia_core::checklist::get_category_id(&pool, project_id, &category_name)
+        .await
+        .map_err(|e| e.to_string())?;
+    verivia_core::linked_excel::acknowledge_change(&pool, cat_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Export the checklist of the project (or of one category) as an xlsx compliance
+/// matrix in the project folder, under a new name for each export (see
+/// `export_file_name`). Without a folder on disk the user picks where to
+/// save it; returns `null` if that dialog was cancelled.
+#[tauri::command]
+async fn export_checklist_xlsx(
+    pool: State<'_, PgPool>,
+    project_id: i32,
+    category_name: Option<String>,
+) -> Result<Value, String> {
+    let file_name = verivia_core::export::export_file_name(&pool, project_id, category_name.as_deref())
+        .await
+        .map_err(|e| e.to_string())?;
+    let folder = verivia_core::projects::get_project_path(&pool, project_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_dir());
+
+    let path = match folder {
+        Some(dir) => dir.join(&file_name),
+        None => {
+            let dialog = FileDialogBuilder::new()
+                .set_title("Salvează matricea de conformitate")
+                .set_file_name(&file_name)
+                .add_filter("Excel", &["xlsx"]);
+            let Some(path) = dialog.save_file() else {
+                return Ok(Value::Null);
+            };
+            path
+        }
+    };
+
+    let export = verivia_core::export::export_checklist(
+        &pool,
+        project_id,
+        category_name.as_deref(),
+        &path.to_string_lossy(),
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+    serde_json::to_value(export).map_err(|e| e.to_string())
+}
+
+/// Find the header row and requirement column without importing anything
+/// (all sheets are tried when `sheet_name` is omitted).
 #[tauri::command]
-fn load_technical_data(file_path: String) -> Result<Value, String> {
-    let items = verivia_core::excel::parse_technical_excel(&file_path)
+fn detect_excel_layout(file_path: String, sheet_name: Option<String>) -> Result<Value, String> {
+    let detection = verivia_core::excel::detect_layout(&file_path, sheet_name.as_deref())
         .map_err(|e| e.to_string())?;
-    serde_json::to_value(items).map_err(|e| e.to_string())
+    serde_json::to_value(detection).map_err(|e| e.to_string())
+}
+
+/// Import with a detection the user confirmed (possibly after adjusting rows / columns).
+#[tauri::command]
+fn load_detected_data(file_path: String, detection: LayoutDetection) -> Result<Value, String> {
+    let parsed = verivia_core::excel::parse_excel_report(&file_path, &detection.profile())
+        .map_err(|e| e.to_string())?;
+    parsed_with_hash(parsed, &file_path)
+}
+
+#[tauri::command]
+async fn list_import_profiles(pool: State<'_, PgPool>) -> Result<Value, String> {
+    let profiles = verivia_core::import_profiles::list_import_profiles(&pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    serde_json::to_value(profiles).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn save_import_profile(pool: State<'_, PgPool>, profile: ImportProfile) -> Result<Value, String> {
+    let saved = verivia_core::import_profiles::save_import_profile(&pool, &profile)
+        .await
+        .map_err(|e| e.to_string())?;
+    serde_json::to_value(saved).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn delete_import_profile(pool: State<'_, PgPool>, id: i32) -> Result<(), String> {
+    verivia_core::import_profiles::delete_import_profile(&pool, id)
+        .await
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -374,9 +1329,10 @@ async fn switch_year(
     let shared = app.state::<SharedWatcher>();
     *shared.lock().unwrap() = None;
     let pool_inner: PgPool = pool.inner().clone();
-    if let Ok(Some(w)) = spawn_dir_watcher(app.clone(), pool_inner, year) {
+    if let Ok(Some(w)) = spawn_dir_watcher(app.clone(), pool_inner.clone(), year.clone()) {
         *shared.lock().unwrap() = Some(w);
     }
+    start_excel_watcher(app.clone(), pool_inner, year, true).await;
     Ok(())
 }
 
@@ -530,6 +1486,7 @@ fn main() {
     tauri::Builder::default()
         .manage(pool.clone())
         .manage::<SharedWatcher>(Arc::new(Mutex::new(None)))
+        .manage(ExcelWatcher(Mutex::new(None)))
         .manage(AppState {
             current_year: Mutex::new(initial_year.clone()),
         })
@@ -553,25 +1510,89 @@ fn main() {
                 }
             });
 
+            // Deadline reminders: once now, then daily
+            spawn_deadline_reminders(handle.clone(), pool_clone.clone());
+            spawn_trash_purge(pool_clone.clone());
+
             // Start watcher
-            if let Ok(Some(w)) = spawn_dir_watcher(handle.clone(), pool_clone, year) {
+            if let Ok(Some(w)) = spawn_dir_watcher(handle.clone(), pool_clone.clone(), year.clone()) {
                 let shared = app.state::<SharedWatcher>();
                 *shared.lock().unwrap() = Some(w);
             }
 
+            // Linked spreadsheets: report changes made while the app was closed, then watch
+            let handle_for_excel = handle.clone();
+            std::thread::spawn(move || {
+                let rt = tokio::runtime::Runtime::new().unwrap();
+                rt.block_on(start_excel_watcher(handle_for_excel, pool_clone, year, true));
+            });
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             auth_login,
             auth_register,
             load_projects,
+            load_category,
+            query_projects,
             add_project,
             edit_project,
+            list_project_date_issues,
+            set_project_metadata,
+            list_procedure_types,
+            search,
+            list_trash,
+            move_to_trash,
+            restore_from_trash,
+            purge_trash,
+            get_trash_retention,
+            set_trash_retention,
+            archive_year,
+            restore_year_archive,
+            list_archived_years,
+            get_year_stats,
+            get_project_stats,
+            list_project_statuses,
+            set_project_status,
+            project_status_history,
+            lock_checklist,
+            unlock_checklist,
+            get_lock_state,
+            lock_history,
+            set_project_deadlines,
+            list_upcoming_deadlines,
+            list_overdue_projects,
+            clone_project,
             delete_project,
+            list_project_templates,
+            save_project_as_template,
             save_checklist,
+            set_item_response,
             save_excel_path,
+            create_category,
+            rename_category,
+            delete_category,
+            reorder_categories,
+            list_projects_by_year,
+            preview_checklist_import,
+            import_checklist,
+            set_category_role,
+            remove_category_role,
             save_project_folder,
             load_technical_data,
+            detect_excel_layout,
+            list_excel_sheets,
+            preview_sheet_import,
+            import_excel_sheets,
+            preview_reimport,
+            apply_reimport,
+            export_checklist_xlsx,
+            check_excel_changes,
+            acknowledge_excel_change,
+            load_detected_data,
+            list_import_profiles,
+            save_import_profile,
+            delete_import_profile,
             list_years,
             get_active_year,
             switch_year,
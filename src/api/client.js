@@ -63,14 +63,122 @@ export const api = {
     register: (mail, password, roles) => call("auth_register", { mail, password, roles }),
 
     // Projects
-    loadProjects: () => call("load_projects"),
-    addProject: (title, date) => call("add_project", { title, date }),
+    // filter (optional): { contractingAuthority, noticeNumber, cpvPrefix, procedureType,
+    //                     currency, minValue, maxValue, lot, owner, statuses: [...] }
+    // → { projects: [{ ..., categories: [{ name, excelPath, total, proposed, verified, complete }] }] }
+    loadProjects: (filter = null) => call("load_projects", { filter }),
+    // → { name, excelPath, checklist: [...] }
+    loadCategory: (projectId, categoryName) =>
+        call("load_category", { projectId, categoryName }),
+    // query: { year, ...filter, search, dateFrom, dateTo (MM.DD.YYYY), completion: "complete" | "incomplete",
+    //          cursor, limit, summary } → { projects: [...], nextCursor }
+    queryProjects: (query = {}) => call("query_projects", { query }),
+    addProject: (title, date, templateId = null) =>
+        call("add_project", { title, date, templateId }),
     editProject: (id, newTitle, newDate) => call("edit_project", { id, newTitle, newDate }),
     deleteProject: (id) => call("delete_project", { id }),
+    listProjectDateIssues: () => call("list_project_date_issues"),
+
+    // Procurement metadata
+    // metadata: { contractingAuthority, noticeNumber, cpvCodes, estimatedValue, currency,
+    //             procedureType, lots, owner }
+    setProjectMetadata: (projectId, metadata) =>
+        call("set_project_metadata", { projectId, metadata }),
+    listProcedureTypes: () => call("list_procedure_types"),
+
+    // Full-text search over project titles, items and notes (diacritics ignored)
+    // → [{ kind: "project" | "item" | "note", projectId, projectTitle, year,
+    //      category, itemId, itemName, snippet, rank }]
+    search: (text, year = null, limit = null) => call("search", { text, year, limit }),
+
+    // Trash — deleted projects, items and notes stay restorable for the retention period
+    // → [{ kind: "project" | "item" | "note", id, label, projectId, projectTitle, year, category, deletedAt }]
+    listTrash: () => call("list_trash"),
+    // kind: "item" (with its sub-tasks) | "note"; ids as in loadCategory
+    moveToTrash: (kind, id) => call("move_to_trash", { kind, id }),
+    restoreFromTrash: (kind, id) => call("restore_from_trash", { kind, id }),
+    purgeTrash: () => call("purge_trash"),
+    getTrashRetention: () => call("get_trash_retention"),
+    setTrashRetention: (days) => call("set_trash_retention", { days }),
+
+    // Year archives — mode is "read_only" (keep the data, block edits) or "remove".
+    // Both prompt for the file and resolve to null if the dialog is cancelled.
+    // → { year, path, checksum, counts: { projects, categories, items, notes,
+    //      statusChanges, dateIssues, lockChanges }, removed }
+    archiveYear: (year, mode = "read_only") => call("archive_year", { year, mode }),
+    restoreYearArchive: () => call("restore_year_archive"),
+    // → [{ year, archivedAt, filePath, checksum, removed }]
+    listArchivedYears: () => call("list_archived_years"),
+
+    // Dashboard — counters, completion %, avg. proposal → verification hours,
+    // flags per user and projects at risk
+    getYearStats: (year = null) => call("get_year_stats", { year }),
+    getProjectStats: (projectId) => call("get_project_stats", { projectId }),
+
+    // Lifecycle status — overrideReason is required to submit with incomplete categories
+    listProjectStatuses: () => call("list_project_statuses"),
+    // token: the caller's login token, recorded as the author of the change
+    setProjectStatus: (token, projectId, status, overrideReason = null) =>
+        call("set_project_status", { token, projectId, status, overrideReason }),
+    projectStatusHistory: (projectId) => call("project_status_history", { projectId }),
+
+    // Locks — a locked project / category has a read-only checklist. Submitting a project
+    // locks it automatically. Omit categoryName to (un)lock the whole project.
+    // Both take the caller's login token; unlocking needs an admin and a reason.
+    lockChecklist: (token, projectId, categoryName = null, reason = null) =>
+        call("lock_checklist", { token, projectId, categoryName, reason }),
+    unlockChecklist: (token, projectId, reason, categoryName = null) =>
+        call("unlock_checklist", { token, projectId, categoryName, reason }),
+    // → { projectId, lockedAt, lockedBy, categories: [{ categoryId, name, lockedAt, lockedBy }] }
+    getLockState: (projectId) => call("get_lock_state", { projectId }),
+    // → [{ category, action: "lock" | "unlock", changedBy, changedAt, reason }]
+    lockHistory: (projectId) => call("lock_history", { projectId }),
+
+    // Deadlines — timestamps as ISO 8601 strings
+    // deadlines: { submissionDeadline, clarificationDeadline, openingDate }
+    setProjectDeadlines: (projectId, deadlines) =>
+        call("set_project_deadlines", { projectId, deadlines }),
+    listUpcomingDeadlines: (days) => call("list_upcoming_deadlines", { days }),
+    listOverdueProjects: () => call("list_overdue_projects"),
+    // The clone goes into the current year. options: { keepFlags, keepNotes, keepExcelPath }
+    // — all default to false
+    cloneProject: (id, newTitle, newDate, options = {}) =>
+        call("clone_project", { id, newTitle, newDate, options }),
+
+    // Project templates
+    listProjectTemplates: () => call("list_project_templates"),
+    saveProjectAsTemplate: (projectId, name, description = null) =>
+        call("save_project_as_template", { projectId, name, description }),
 
     // Checklist — save a single category's checklist
-    saveChecklist: (projectId, categoryName, items) =>
-        call("save_checklist", { projectId, categoryName, items }),
+    // (items carry their response and documentReference along with the flags).
+    // excelHash: from loadTechnicalData when the items were just read from the category's Excel
+    saveChecklist: (projectId, categoryName, items, excelHash = null) =>
+        call("save_checklist", { projectId, categoryName, items, excelHash }),
+    // One requirement's response, without saving the whole checklist (null / blank clears)
+    setItemResponse: (itemId, response, documentReference = null) =>
+        call("set_item_response", { itemId, response, documentReference }),
+
+    // Categories
+    createCategory: (projectId, name) => call("create_category", { projectId, name }),
+    renameCategory: (projectId, categoryName, newName) =>
+        call("rename_category", { projectId, categoryName, newName }),
+    // Only an empty category can be deleted (its items go to the trash first)
+    deleteCategory: (projectId, categoryName) =>
+        call("delete_category", { projectId, categoryName }),
+    reorderCategories: (projectId, categoryNames) =>
+        call("reorder_categories", { projectId, categoryNames }),
+    listProjectsByYear: (year) => call("list_projects_by_year", { year }),
+    previewChecklistImport: (sourceProjectId, sourceCategoryName, projectId, categoryName) =>
+        call("preview_checklist_import", { sourceProjectId, sourceCategoryName, projectId, categoryName }),
+    // options: { mode: "append" | "replace", keepFlags, keepNotes }
+    importChecklist: (sourceProjectId, sourceCategoryName, projectId, categoryName, options = {}) =>
+        call("import_checklist", { sourceProjectId, sourceCategoryName, projectId, categoryName, options }),
+    // Roles are granted and revoked by admins only (their login token)
+    setCategoryRole: (token, userId, category, roles) =>
+        call("set_category_role", { token, userId, category, roles }),
+    removeCategoryRole: (token, userId, category) =>
+        call("remove_category_role", { token, userId, category }),
 
     // Years
     listYears: () => call("list_years"),
@@ -85,9 +193,56 @@ export const api = {
     loadUsers: () => call("load_users"),
 
     // Excel (desktop-only)
-    loadTechnicalData: (filePath) => call("load_technical_data", { filePath }),
-    saveExcelPath: (projectId, path) =>
-        call("save_excel_path", { projectId, path }),
+    // → { checklist, report, excelHash } — report: { sheetName, rowsRead, rowsImported, emptyRows,
+    //     issues: [{ kind: "skippedRow"|"convertedCell"|"mergedCell"|"duplicateName"|"longCell",
+    //     row, column, message }] } (positions 1-based); loadDetectedData returns the same
+    loadTechnicalData: (filePath, profileId = null) =>
+        call("load_technical_data", { filePath, profileId }),
+    // Layout detection — confirm (or adjust) the result, then import it
+    // → { sheetName, sheetIndex, headerRow, nameColumn, nameTitle, referenceColumn,
+    //     responseColumn, documentReferenceColumn, dataRows, sample: [text], confidence: 0..1 }
+    detectExcelLayout: (filePath, sheetName = null) =>
+        call("detect_excel_layout", { filePath, sheetName }),
+    loadDetectedData: (filePath, detection) =>
+        call("load_detected_data", { filePath, detection }),
+    // Multi-sheet workbooks — map each sheet to a category; the import is all or nothing.
+    // Categories that already have items are merged like applyReimport (progress kept)
+    // → [{ name, index, rows }]
+    listExcelSheets: (filePath) => call("list_excel_sheets", { filePath }),
+    // mappings: [{ sheetName, categoryName, profileId? }] (no profileId = detected layout)
+    // → [{ sheetName, categoryName, itemCount, checklist, report }] (report as for loadTechnicalData)
+    previewSheetImport: (projectId, filePath, mappings) =>
+        call("preview_sheet_import", { projectId, filePath, mappings }),
+    importExcelSheets: (projectId, filePath, mappings) =>
+        call("import_excel_sheets", { projectId, filePath, mappings }),
+    // Re-import an updated specification, keeping progress on matched requirements
+    // (profileId null = read it with the sheet and profile of its last import)
+    // → { added, removed, renamed, unchanged, changes: [{ kind: "added"|"removed"|"renamed"|"unchanged",
+    //     itemId, depth, reference, name, previousReference, previousName, similarity,
+    //     proposed, verified, notes }] }
+    previewReimport: (projectId, categoryName, filePath, profileId = null) =>
+        call("preview_reimport", { projectId, categoryName, filePath, profileId }),
+    applyReimport: (projectId, categoryName, filePath, profileId = null) =>
+        call("apply_reimport", { projectId, categoryName, filePath, profileId }),
+    // Linked spreadsheets of the current year changed since their import (desktop also
+    // pushes the same list as the "excel_changed" event when a file is saved)
+    // → [{ projectId, projectTitle, categoryName, excelPath, diff: { added, removed, ... } }]
+    checkExcelChanges: () => call("check_excel_changes"),
+    // Keep the checklist; the file's current content counts as imported
+    acknowledgeExcelChange: (projectId, categoryName) =>
+        call("acknowledge_excel_change", { projectId, categoryName }),
+    // Compliance matrix xlsx, saved in the project folder (asks where if it has none)
+    // categoryName null = every category → { path, sheets, items } or null if cancelled
+    exportChecklistXlsx: (projectId, categoryName = null) =>
+        call("export_checklist_xlsx", { projectId, categoryName }),
+    // Import profiles — rows / columns / sheetIndex are 1-based; headerRow null = auto-detect
+    // → [{ id, name, sheetName, sheetIndex, headerRow, nameColumn,
+    //      descriptionColumn, referenceColumn, responseColumn, documentReferenceColumn, endMarker }]
+    listImportProfiles: () => call("list_import_profiles"),
+    saveImportProfile: (profile) => call("save_import_profile", { profile }), // id 0 = new
+    deleteImportProfile: (id) => call("delete_import_profile", { id }),
+    saveExcelPath: (projectId, path, categoryName = "Tehnic") =>
+        call("save_excel_path", { projectId, path, categoryName }),
 
     // Folder (desktop-only)
     openFolder: (path) => call("open_folder", { path }),
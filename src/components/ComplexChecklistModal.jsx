@@ -124,7 +124,6 @@ export default function ComplexChecklistModal({
   // -----------------------------------------------------
   // HOOKS & STATE
   // -----------------------------------------------------
-  const [dbData, setDbData]               = useState(null);
   const [items, setItems]                 = useState([]);
   const [expanded, setExpanded]           = useState([]);
   const [newMainTask, setNewMainTask]     = useState("");
@@ -135,7 +134,7 @@ export default function ComplexChecklistModal({
   const [showCloseConfirm, setShowCloseConfirm] = useState(false);
   const [hasUnsavedChanges, setHasUnsavedChanges] = useState(false);
 
-  const [editingTask, setEditingTask]     = useState(null); // {type,itemIndex,subIndex,originalName}
+  const [editingTask, setEditingTask]     = useState(null); // {path,originalName}
   const [editName, setEditName]           = useState("");
 
   const [showNotesDialog, setShowNotesDialog] = useState(false);
@@ -160,6 +159,34 @@ export default function ComplexChecklistModal({
     return { checked: all, indeterminate: some && !all };
   };
 
+  // Titlul afișat: referința din caiet ("1.2", "b)") urmată de nume
+  const taskTitle = (t) => (t.reference ? `${t.reference} ${t.name}` : t.name);
+  // needsReview: textul s-a schimbat la actualizarea din Excel, verificarea a fost resetată
+  const taskLabel = (t) => (
+    <>
+      {t.reference && (
+        <Typography component="span" sx={{ fontWeight: 600, color: "text.secondary", mr: 1 }}>
+          {t.reference}
+        </Typography>
+      )}
+      {t.name}
+      {t.needsReview && (
+        <Tooltip
+          arrow
+          title={t.previousName ? `Text anterior: ${t.previousName}` : "Text modificat la actualizarea din Excel"}
+        >
+          <Typography
+            component="span"
+            sx={{ ml: 1, px: .5, fontSize: 12, fontWeight: 700, color: "#b26a00",
+                  border: "1px solid #b26a00", borderRadius: 1 }}
+          >
+            Modificat – de reverificat
+          </Typography>
+        </Tooltip>
+      )}
+    </>
+  );
+
   const toB64 = (ab) => {
     const bytes = new Uint8Array(ab);
     const CHUNK = 0x8000;
@@ -328,13 +355,12 @@ export default function ComplexChecklistModal({
       }
     };
 
-    items.forEach((t) => {
+    const addTask = (t, lvl) => {
       const hasSubs = t.subTasks && t.subTasks.length > 0;
-      addRow(t.name, 0, hasSubs ? null : { proposedBy: t.proposedBy, verifiedBy: t.verifiedBy });
-      t.subTasks?.forEach((s) =>
-        addRow(s.name, 1, { proposedBy: s.proposedBy, verifiedBy: s.verifiedBy })
-      );
-    });
+      addRow(taskTitle(t), lvl, hasSubs ? null : { proposedBy: t.proposedBy, verifiedBy: t.verifiedBy });
+      t.subTasks?.forEach((s) => addTask(s, lvl + 1));
+    };
+    items.forEach((t) => addTask(t, 0));
 
     /* -------------------- FOOTER PE TOATE PAGINILE -------------------- */
     const totalPages = doc.internal.getNumberOfPages();
@@ -368,33 +394,26 @@ export default function ComplexChecklistModal({
       img.onload = () => res(img);
     });
 
+  // Starea se calculează din toate frunzele, la orice adâncime
+  const leafFlags = (item, flag) =>
+    item.subTasks?.length ? item.subTasks.flatMap((st) => leafFlags(st, flag)) : [!!item[flag]];
+
   const getFlagState = (item, flag) => {
     if (!item.subTasks || item.subTasks.length === 0)
       return { checked: item[flag], indeterminate: false };
-    const all  = item.subTasks.every((st) => st[flag]);
-    const some = item.subTasks.some((st) => st[flag]);
+    const flags = leafFlags(item, flag);
+    const all  = flags.every(Boolean);
+    const some = flags.some(Boolean);
     return { checked: all, indeterminate: some && !all };
   };
 
   // -----------------------------------------------------
-  // 1) Load data
+  // 1) Load data (părintele încarcă lista categoriei cu api.loadCategory)
   // -----------------------------------------------------
   useEffect(() => {
     async function fetchData() {
       try {
-        const data = await api.loadProjects();
-        setDbData(data);
-
-        const project  = data.projects?.find((p) => p.title === projectTitle);
-        if (!project) return;
-
-        const category = project.categories?.find((c) => c.name === categoryName);
-        if (!category) return;
-
-        const raw =
-(categoryName === "Tehnic" && initialTasks?.length)      ? initialTasks
-: (category.checklist?.length)                             ? category.checklist
-                                                           : initialTasks;
+        const raw = initialTasks ?? [];
 
         const addFlags = (t) => ({
           ...t,
@@ -455,19 +474,21 @@ export default function ComplexChecklistModal({
   // -----------------------------------------------------
   // 4) Toggle flag (rol‑aware)
   // -----------------------------------------------------
-  const toggleFlag = (pIdx, sIdx, flag, val) => {
+  // Un task e adresat prin calea de indici: [i] principal, [i, j] subtask, [i, j, k] ...
+  const nodeAt = (list, path) => path.slice(1).reduce((t, k) => t.subTasks[k], list[path[0]]);
+  const samePath = (a, b) => !!a && !!b && a.join(".") === b.join(".");
+
+  const toggleFlag = (path, flag, val) => {
     if (isVerificator && flag === "proposed") return;
     if (isEditor      && flag === "verified") return;
 
     if (flag === "verified" && val) {
-      const target = sIdx == null ? items[pIdx] : items[pIdx].subTasks[sIdx];
-      if (!target.proposed) return;
+      if (!nodeAt(items, path).proposed) return;
     }
 
     setHasUnsavedChanges(true);
     setItems((prev) => {
       const clone = structuredClone(prev);
-      const parent = clone[pIdx];
       const byField = flag === "proposed" ? "proposedBy" : "verifiedBy";
       const stamp = val ? (userName || null) : null;
 
@@ -475,21 +496,18 @@ export default function ComplexChecklistModal({
         t.status = t.proposed && t.verified ? "complete" : "incomplete";
       };
 
-      if (sIdx == null) {
-        parent[flag] = val;
-        parent[byField] = stamp;
-        parent.subTasks.forEach((st) => {
-          st[flag] = val;
-          st[byField] = stamp;
-          updateStatus(st);
-        });
-        updateStatus(parent);
-      } else {
-        const sub = parent.subTasks[sIdx];
-        sub[flag] = val;
-        sub[byField] = stamp;
-        updateStatus(sub);
+      // Task-ul și toți descendenții lui
+      const setDown = (t) => {
+        t[flag] = val;
+        t[byField] = stamp;
+        t.subTasks.forEach(setDown);
+        updateStatus(t);
+      };
+      setDown(nodeAt(clone, path));
 
+      // Părinții, de jos în sus
+      for (let d = path.length - 1; d > 0; d--) {
+        const parent = nodeAt(clone, path.slice(0, d));
         parent[flag] = parent.subTasks.length > 0 &&
                        parent.subTasks.every((st) => st[flag]);
         // Parent attribution: if all subs agree, reflect them; else clear.
@@ -504,16 +522,13 @@ export default function ComplexChecklistModal({
       return clone;
     });
 
-    addAuditLog(pIdx, sIdx, `${flag} → ${val}`);
+    addAuditLog(path, `${flag} → ${val}`);
   };
 
   /* ───────── helpers note ───────── */
 const currentObj = (draft = items) => {
   if (!notesTarget) return null;
-  const { type, itemIndex, subIndex } = notesTarget;
-  return type === "parent"
-    ? draft[itemIndex]
-    : draft[itemIndex].subTasks[subIndex];
+  return nodeAt(draft, notesTarget.path);
 };
 
 const mutateNotes = (fn) => {
@@ -610,26 +625,24 @@ const deleteNote = (idx) => {
     setHasUnsavedChanges(true);
     setItems((prev) => prev.filter((_, i) => i !== idx));
   };
-  const handleDeleteSubtask = (pi, si) => {
+  const handleDeleteSubtask = (path) => {
     if (!canMutate) return;
     setHasUnsavedChanges(true);
     setItems((prev) => {
       const n = structuredClone(prev);
-      n[pi].subTasks.splice(si, 1);
+      nodeAt(n, path.slice(0, -1)).subTasks.splice(path[path.length - 1], 1);
       return n;
     });
   };
 
-  const startEdit = (type, i, j, name) =>
-    canMutate && setEditingTask({ type, itemIndex: i, subIndex: j, originalName: name });
+  const startEdit = (path, name) =>
+    canMutate && setEditingTask({ path, originalName: name });
   const saveEdit = () => {
     if (!editingTask || !editName.trim()) return;
     setHasUnsavedChanges(true);
     setItems((prev) => {
       const n = structuredClone(prev);
-      if (editingTask.type === "parent")
-        n[editingTask.itemIndex].name = editName;
-      else n[editingTask.itemIndex].subTasks[editingTask.subIndex].name = editName;
+      nodeAt(n, editingTask.path).name = editName;
       return n;
     });
     setEditingTask(null);
@@ -639,8 +652,8 @@ const deleteNote = (idx) => {
   // -----------------------------------------------------
   // 7) Note & audit
   // -----------------------------------------------------
-  const openNotes = (type, i, j) => {
-    setNotesTarget({ type, itemIndex: i, subIndex: j });
+  const openNotes = (path) => {
+    setNotesTarget({ path });
     setNotesValue("");
     setShowNotesDialog(true);
   };
@@ -667,10 +680,10 @@ const deleteNote = (idx) => {
     setEditingNote(null);
   };
 
-  const addAuditLog = (itemIndex, subIndex, action) =>
+  const addAuditLog = (path, action) =>
     setAuditLog((p) => [
       ...p,
-      { time: new Date().toLocaleString(), user: userName, itemIndex, subIndex, action },
+      { time: new Date().toLocaleString(), user: userName, path, action },
     ]);
 
   // -----------------------------------------------------
@@ -692,35 +705,33 @@ const deleteNote = (idx) => {
   // -----------------------------------------------------
   // 9) Filtrare & progres
   // -----------------------------------------------------
+  // Fiecare task filtrat își păstrează calea din `items` (path)
   const getFilteredItems = () => {
-    if (!searchTerm.trim()) return items;
-    const kw = searchTerm.toLowerCase();
-    return items
-      .map((it) => {
-        const nameMatch = it.name.toLowerCase().includes(kw);
-        const newSubs =
-          it.subTasks?.filter((s) => s.name.toLowerCase().includes(kw)) ?? [];
-        if (nameMatch || newSubs.length) return { ...it, subTasks: newSubs };
-        return null;
-      })
-      .filter(Boolean);
+    const kw = searchTerm.trim().toLowerCase();
+    const filterTask = (t, path) => {
+      const subs = (t.subTasks ?? [])
+        .map((s, j) => filterTask(s, [...path, j]))
+        .filter(Boolean);
+      const match = !kw || taskTitle(t).toLowerCase().includes(kw);
+      return match || subs.length ? { ...t, path, subTasks: subs } : null;
+    };
+    return items.map((it, i) => filterTask(it, [i])).filter(Boolean);
   };
   const filteredItems = getFilteredItems();
 
   const allTasks = [];
-  items.forEach((it) => {
-    allTasks.push(it);
-    it.subTasks?.forEach((s) => allTasks.push(s));
-  });
+  const collect = (t) => {
+    allTasks.push(t);
+    t.subTasks?.forEach(collect);
+  };
+  items.forEach(collect);
   const completedCount = allTasks.filter((t) => t.status === "complete").length;
   const totalCount     = allTasks.length;
   const progressPercent = totalCount ? (completedCount / totalCount) * 100 : 0;
   const allComplete     = completedCount === totalCount && totalCount > 0;
 
   const renderNotes = () => {
-    const { type, itemIndex, subIndex } = notesTarget;
-    const obj = type === 'parent' ? items[itemIndex]
-                                  : items[itemIndex].subTasks[subIndex];
+    const obj = currentObj();
 
     if (!obj.notes.length) {
       return <Typography variant="body2" color="text.secondary">Nu există note.</Typography>;
@@ -748,6 +759,99 @@ const deleteNote = (idx) => {
     ));
   };
 
+  // Subtask la orice adâncime; descendenții lui se afișează indentați dedesubt
+  const renderSubTask = (sub) => (
+    <Box key={sub.path.join(".")}>
+      <Box sx={{ display: "flex", alignItems: "center", mb: 1 }}>
+        {samePath(editingTask?.path, sub.path) ? (
+          <>
+            <TextField
+              size="small"
+              value={editName}
+              onChange={(e) => setEditName(e.target.value)}
+              sx={{ flex: 1, mr: 1 }}
+            />
+            <Button
+              variant="contained"
+              onClick={saveEdit}
+              sx={{ mr: 1 }}
+            >
+              Salvare
+            </Button>
+            <Button onClick={cancelEdit}>Anulează</Button>
+          </>
+        ) : (
+          <FormControlLabel
+            sx={{ flex: 1 }}
+            label={taskLabel(sub)}
+            control={
+              <Box sx={{ display: "flex", gap: .5 }}>
+                {/* proposed */}
+                <LockedCheckbox
+                  lock={isVerificator}
+                  checked={getFlagState(sub, "proposed").checked}
+                  indeterminate={getFlagState(sub, "proposed").indeterminate}
+                  disabled={isVerificator}
+                  onChange={(e) =>
+                    toggleFlag(sub.path, "proposed", e.target.checked)
+                  }
+                  sx={{ "&.Mui-checked": { color: "#1976d2" } }}
+                />
+                {/* verified */}
+                <LockedCheckbox
+                  lock={isEditor}
+                  checked={getFlagState(sub, "verified").checked}
+                  indeterminate={getFlagState(sub, "verified").indeterminate}
+                  disabled={isEditor || !sub.proposed}
+                  onChange={(e) =>
+                    toggleFlag(sub.path, "verified", e.target.checked)
+                  }
+                  sx={{ "&.Mui-checked": { color: "seagreen" } }}
+                />
+              </Box>
+            }
+          />
+        )}
+
+        {isEditor && (
+          <>
+          <ActionIcon
+            title="Editare"
+            onClick={() => startEdit(sub.path, sub.name)}
+          >
+            <EditIcon fontSize="inherit" />
+          </ActionIcon>
+
+          <ActionIcon
+            title="Ștergere"
+            color="red"
+            onClick={() => handleDeleteSubtask(sub.path)}
+          >
+            <DeleteIcon fontSize="inherit" />
+          </ActionIcon>
+        </>
+        )}
+        <ActionIcon title="Note" onClick={() => openNotes(sub.path)}>
+          <Badge
+            color="error"
+            overlap="circular"
+            badgeContent="!"
+            invisible={!hasNotes(sub)}
+            anchorOrigin={{ vertical: "top", horizontal: "right" }}
+          >
+            <NoteIcon
+              fontSize="inherit"
+              sx={{ color: hasNotes(sub) ? "primary.main" : "inherit" }}
+            />
+          </Badge>
+        </ActionIcon>
+      </Box>
+      {sub.subTasks?.length > 0 && (
+        <Box sx={{ ml: 4 }}>{sub.subTasks.map(renderSubTask)}</Box>
+      )}
+    </Box>
+  );
+
   // -----------------------------------------------------
   // 10) UI
   // -----------------------------------------------------
@@ -872,8 +976,8 @@ const deleteNote = (idx) => {
           }}
         >
           <FormGroup>
-            {filteredItems.map((item, i) => (
-              <Box key={i} sx={{ border: "1px solid #ccc", p: 1, mb: 2 }}>
+            {filteredItems.map((item) => (
+              <Box key={item.path[0]} sx={{ border: "1px solid #ccc", p: 1, mb: 2 }}>
                 {/* Parent */}
                 <Box sx={{ display: "flex", alignItems: "center" }}>
                   <Badge
@@ -882,14 +986,12 @@ const deleteNote = (idx) => {
                     invisible={!item.subTasks?.length}
                     sx={{ mr: 1 }}
                   >
-                    <IconButton size="small" onClick={() => toggleExpand(i)}>
-                      {isExpanded(i) ? <ArrowDropDownIcon /> : <ArrowRightIcon />}
+                    <IconButton size="small" onClick={() => toggleExpand(item.path[0])}>
+                      {isExpanded(item.path[0]) ? <ArrowDropDownIcon /> : <ArrowRightIcon />}
                     </IconButton>
                   </Badge>
 
-                  {editingTask &&
-                  editingTask.type === "parent" &&
-                  editingTask.itemIndex === i ? (
+                  {samePath(editingTask?.path, item.path) ? (
                     <>
                       <TextField
                         size="small"
@@ -905,7 +1007,7 @@ const deleteNote = (idx) => {
                   ) : (
                     <FormControlLabel
                       sx={{ flex: 1 }}
-                      label={item.name}
+                      label={taskLabel(item)}
                       control={
                         <Box sx={{ display: "flex", gap: .5 }}>
                           {/* proposed */}
@@ -915,7 +1017,7 @@ const deleteNote = (idx) => {
                             indeterminate={getFlagState(item, "proposed").indeterminate}
                             disabled={isVerificator}
                             onChange={(e) =>
-                              toggleFlag(i, null, "proposed", e.target.checked)
+                              toggleFlag(item.path, "proposed", e.target.checked)
                             }
                             sx={{ "&.Mui-checked": { color: "#1976d2" } }}
                           />
@@ -926,7 +1028,7 @@ const deleteNote = (idx) => {
                             indeterminate={getFlagState(item, "verified").indeterminate}
                             disabled={isEditor || !item.proposed}
                             onChange={(e) =>
-                              toggleFlag(i, null, "verified", e.target.checked)
+                              toggleFlag(item.path, "verified", e.target.checked)
                             }
                             sx={{ "&.Mui-checked": { color: "seagreen" } }}
                           />
@@ -939,14 +1041,14 @@ const deleteNote = (idx) => {
                     <>
                       <ActionIcon
                         title="Editare"
-                        onClick={() => startEdit("parent", i, null, item.name)}
+                        onClick={() => startEdit(item.path, item.name)}
                       >
                         <EditIcon fontSize="inherit" />
                       </ActionIcon>
                       <ActionIcon
                         title="Ștergere"
                         color="red"
-                        onClick={() => handleDeleteTask(i)}
+                        onClick={() => handleDeleteTask(item.path[0])}
                       >
                         <DeleteIcon fontSize="inherit" />
                       </ActionIcon>
@@ -954,7 +1056,7 @@ const deleteNote = (idx) => {
                   )}
                   <ActionIcon
                     title="Note"
-                    onClick={() => openNotes("parent", i, null)}
+                    onClick={() => openNotes(item.path)}
                   >
                     <Badge
                       overlap="circular"
@@ -981,100 +1083,11 @@ const deleteNote = (idx) => {
                 </Box>
 
                 {/* Subtasks */}
-                {isExpanded(i) && (
+                {isExpanded(item.path[0]) && (
                   <Box sx={{ ml: 8, mt: 1 }}>
                     {item.subTasks?.length ? (
                       <Box sx={{ mb: 2 }}>
-                        {item.subTasks.map((sub, j) => (
-                          <Box
-                            key={j}
-                            sx={{ display: "flex", alignItems: "center", mb: 1 }}
-                          >
-                            {editingTask &&
-                            editingTask.type === "child" &&
-                            editingTask.itemIndex === i &&
-                            editingTask.subIndex === j ? (
-                              <>
-                                <TextField
-                                  size="small"
-                                  value={editName}
-                                  onChange={(e) => setEditName(e.target.value)}
-                                  sx={{ flex: 1, mr: 1 }}
-                                />
-                                <Button
-                                  variant="contained"
-                                  onClick={saveEdit}
-                                  sx={{ mr: 1 }}
-                                >
-                                  Salvare
-                                </Button>
-                                <Button onClick={cancelEdit}>Anulează</Button>
-                              </>
-                            ) : (
-                              <FormControlLabel
-                                sx={{ flex: 1 }}
-                                label={sub.name}
-                                control={
-                                  <Box sx={{ display: "flex", gap: .5 }}>
-                                    {/* proposed */}
-                                    <LockedCheckbox
-                                      lock={isVerificator}
-                                      checked={sub.proposed}
-                                      disabled={isVerificator}
-                                      onChange={(e) =>
-                                        toggleFlag(i, j, "proposed", e.target.checked)
-                                      }
-                                      sx={{ "&.Mui-checked": { color: "#1976d2" } }}
-                                    />
-                                    {/* verified */}
-                                    <LockedCheckbox
-                                      lock={isEditor}
-                                      checked={sub.verified}
-                                      disabled={isEditor || !sub.proposed}
-                                      onChange={(e) =>
-                                        toggleFlag(i, j, "verified", e.target.checked)
-                                      }
-                                      sx={{ "&.Mui-checked": { color: "seagreen" } }}
-                                    />
-                                  </Box>
-                                }
-                              />
-                            )}
-
-                            {isEditor && (
-                              <>
-                              <ActionIcon
-                                title="Editare"
-                                onClick={() => startEdit("child", i, j, sub.name)}
-                              >
-                                <EditIcon fontSize="inherit" />
-                              </ActionIcon>
-
-                              <ActionIcon
-                                title="Ștergere"
-                                color="red"
-                                onClick={() => handleDeleteSubtask(i, j)}
-                              >
-                                <DeleteIcon fontSize="inherit" />
-                              </ActionIcon>
-                            </>
-                            )}
-                            <ActionIcon title="Note" onClick={() => openNotes("child", i, j)}>
-                              <Badge
-                                color="error"
-                                overlap="circular"
-                                badgeContent="!"
-                                invisible={!hasNotes(sub)}
-                                anchorOrigin={{ vertical: "top", horizontal: "right" }}
-                              >
-                                <NoteIcon
-                                  fontSize="inherit"
-                                  sx={{ color: hasNotes(sub) ? "primary.main" : "inherit" }}
-                                />
-                              </Badge>
-                            </ActionIcon>
-                          </Box>
-                        ))}
+                        {item.subTasks.map(renderSubTask)}
                       </Box>
                     ) : (
                       <Typography variant="body2" color="text.secondary">
@@ -1091,7 +1104,7 @@ const deleteNote = (idx) => {
                           value={newSubtask}
                           onChange={(e) => setNewSubtask(e.target.value)}
                         />
-                        <Button variant="contained" onClick={() => handleAddSubtask(i)}>
+                        <Button variant="contained" onClick={() => handleAddSubtask(item.path[0])}>
                           + Add Subtask
                         </Button>
                       </Box>
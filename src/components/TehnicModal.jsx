@@ -12,6 +12,56 @@ import excelIcon from "../images/excel.png";
 import VisibilityIcon from "@mui/icons-material/Visibility";
 import VisibilityOffIcon from "@mui/icons-material/VisibilityOff";
 
+/* mesajul de după import: ce s-a sărit, convertit sau pare suspect */
+const MAX_LISTED_ISSUES = 15;
+
+function importSummary(report) {
+  const lines = [
+    `Excel încărcat: ${report.rowsImported} cerințe din ${report.rowsRead} rânduri` +
+      (report.emptyRows ? ` (${report.emptyRows} rânduri goale).` : "."),
+  ];
+  if (report.issues.length) {
+    lines.push("", `De verificat înainte de salvare (${report.issues.length}):`);
+    report.issues
+      .slice(0, MAX_LISTED_ISSUES)
+      .forEach((i) => lines.push(`• Rândul ${i.row}: ${i.message}`));
+    if (report.issues.length > MAX_LISTED_ISSUES) {
+      lines.push(`… și încă ${report.issues.length - MAX_LISTED_ISSUES}`);
+    }
+  }
+  return lines.join("\n");
+}
+
+/* confirmarea actualizării: ce se adaugă, ce se modifică și ce ajunge în coș */
+function reimportSummary(diff) {
+  const lines = [
+    `Actualizare din Excel: ${diff.added} cerințe noi, ${diff.renamed} modificate, ` +
+      `${diff.removed} eliminate, ${diff.unchanged} neschimbate.`,
+  ];
+  const listChanges = (title, changes, format) => {
+    if (!changes.length) return;
+    lines.push("", title);
+    changes.slice(0, MAX_LISTED_ISSUES).forEach((c) => lines.push(`• ${format(c)}`));
+    if (changes.length > MAX_LISTED_ISSUES) {
+      lines.push(`… și încă ${changes.length - MAX_LISTED_ISSUES}`);
+    }
+  };
+  listChanges(
+    "Modificate (verificarea se resetează și vor fi marcate de reverificat):",
+    diff.changes.filter((c) => c.kind === "renamed"),
+    (c) => `${c.previousName ?? ""} → ${c.name}`
+  );
+  listChanges(
+    "Eliminate (mutate în coș):",
+    diff.changes.filter((c) => c.kind === "removed"),
+    (c) => c.name + (c.notes ? ` (${c.notes} note)` : "")
+  );
+  lines.push("", "Aplicați actualizarea?");
+  return lines.join("\n");
+}
+
+const CATEGORY = "Tehnic";
+
 export default function TehnicModal({
   mode = "editor",
   initialTasks: propTasks,
@@ -25,20 +75,20 @@ export default function TehnicModal({
 }) {
   const [loadingExcel, setLoadingExcel] = useState(false);
   const [excelData, setExcelData] = useState(null);
+  // hash-ul fișierului din care a fost citit excelData; salvat împreună cu lista
+  const [excelHash, setExcelHash] = useState(null);
   const [excelPath, setExcelPath] = useState(initialPath);
   const [showExcelBanner, setShowExcelBanner] = useState(!!initialPath);
   const [iconHovered, setIconHovered] = useState(false);
 
   const isEditor = mode === "editor";
 
-  // sincronizează excelPath local cu prop
+  // sincronizează excelPath local cu prop; lista rămâne cea salvată, iar bannerul
+  // oferă actualizarea din fișier (cu raportul importului)
   useEffect(() => {
     if (initialPath !== excelPath) {
       setExcelPath(initialPath);
-      if (initialPath) {
-        // Reîncarcă datele Excel dacă initialPath s-a schimbat
-        loadExcel(initialPath, true); // silent=true pentru a nu afișa alert
-      }
+      setShowExcelBanner(!!initialPath);
     }
   }, [initialPath]);
 
@@ -64,7 +114,7 @@ export default function TehnicModal({
     if (!IS_TAURI) return; // Excel import is desktop-only
     const { open: openDialog } = await import("@tauri-apps/api/dialog");
     const filePath = await openDialog({
-      filters: [{ name: "Excel Files", extensions: ["xlsx", "xls"] }],
+      filters: [{ name: "Cerințe (Excel, ODS, CSV, Word)", extensions: ["xlsx", "xls", "ods", "csv", "docx"] }],
       multiple: false,
     });
     if (filePath) {
@@ -72,28 +122,46 @@ export default function TehnicModal({
     }
   };
 
+  /* actualizare: cerințele potrivite își păstrează bifele, notele și răspunsurile */
   const handleUpdateExcel = async () => {
-    if (excelPath) {
-      await loadExcel(excelPath);
+    if (!excelPath) return;
+    setLoadingExcel(true);
+    try {
+      const diff = await api.previewReimport(projectId, CATEGORY, excelPath);
+      const changed =
+        diff.added + diff.removed + diff.renamed > 0 ||
+        diff.changes.some((c) => c.previousReference);
+      if (!changed) {
+        alert("Excelul nu conține modificări față de lista actuală.");
+      } else if (window.confirm(reimportSummary(diff))) {
+        await api.applyReimport(projectId, CATEGORY, excelPath);
+        const category = await api.loadCategory(projectId, CATEGORY);
+        setExcelData(category.checklist);
+        setExcelHash(null); // importul a fost deja înregistrat
+        setShowExcelBanner(false);
+      }
+    } catch (err) {
+      console.error("Eroare la actualizarea din Excel:", err);
+      alert("Eroare la actualizarea din Excel.");
     }
+    setLoadingExcel(false);
   };
 
   /* loader comun */
-  const loadExcel = async (filePath, silent = false) => {
+  const loadExcel = async (filePath) => {
     setLoadingExcel(true);
     try {
-      const data = await api.loadTechnicalData(filePath);
-      setExcelData(data);
+      const { checklist, report, excelHash } = await api.loadTechnicalData(filePath);
+      setExcelData(checklist);
+      setExcelHash(excelHash);
       setExcelPath(filePath);
 
       await api.saveExcelPath(projectId, filePath);
       onExcelPathSaved?.(filePath);
 
-      if (!silent) {
-        // ascund banner după load
-        setShowExcelBanner(false);
-      }
-      if (!silent) alert("Excel loaded successfully!");
+      // ascund banner după load
+      setShowExcelBanner(false);
+      alert(importSummary(report));
     } catch (err) {
       console.error("Error loading Excel data:", err);
       alert("Eroare la încărcarea Excel.");
@@ -108,9 +176,9 @@ export default function TehnicModal({
       {...props}
       open={open}
       onClose={onClose}
-      onConfirm={onConfirm}
+      onConfirm={onConfirm && ((tasks) => onConfirm(tasks, excelHash))}
       mode={mode}
-      categoryName="Tehnic"
+      categoryName={CATEGORY}
       initialTasks={initialTasks}
       excelPath={excelPath}
     >
@@ -158,7 +226,7 @@ export default function TehnicModal({
                 </Button>
 
                 <Tooltip
-                  title={excelPath ? "Reîncarcă fișierul existent" : "Nu există încă un Excel"}
+                  title={excelPath ? "Actualizează din fișierul existent (progresul se păstrează)" : "Nu există încă un Excel"}
                   arrow
                 >
                   <span>
@@ -66,6 +66,12 @@ export default function ProjectsView() {
     catIdx: null,
   });
 
+  /* ───────────── Excel legat modificat pe disc ───────────── */
+  // [{ projectId, projectTitle, categoryName, excelPath, diff }] — vezi api.checkExcelChanges
+  const [excelChanges, setExcelChanges] = useState([]);
+  const [excelChangesHidden, setExcelChangesHidden] = useState(false);
+  const [applyingExcel, setApplyingExcel] = useState(false);
+
   /* ───────────── dialog: folder nou pentru anul X ───────────── */
   const [newYearDialog, setNewYearDialog] = useState({ open: false, year: "" });
   const [pickingFolder, setPickingFolder] = useState(false);
@@ -75,6 +81,8 @@ export default function ProjectsView() {
   const [newProjectTitle, setNewProjectTitle] = useState("");
   const [newProjectDate, setNewProjectDate] = useState("");
   const [selectedProject, setSelectedProject] = useState(null);
+  // lista categoriei deschise — load_projects întoarce doar contoarele
+  const [categoryTasks, setCategoryTasks] = useState([]);
   const [showEligibilityModal, setShowEligibilityModal] = useState(false);
   const [showFinanciarModal, setShowFinanciarModal] = useState(false);
   const [showPteModal, setShowPteModal] = useState(false);
@@ -154,6 +162,49 @@ const handleAddYear = async () => {
     if (!String(e).startsWith("Anulat")) console.error("add_year:", e);
   }
 };
+  // Înlocuiește modificările deja afișate pentru aceleași categorii
+  const mergeExcelChanges = (incoming, replaceAll = false) => {
+    setExcelChanges((prev) => [
+      ...(replaceAll ? [] : prev).filter(
+        (c) => !incoming.some((n) => n.projectId === c.projectId && n.categoryName === c.categoryName)
+      ),
+      ...incoming,
+    ]);
+    if (incoming.length) setExcelChangesHidden(false);
+  };
+  const checkExcelChanges = () =>
+    api.checkExcelChanges()
+      .then((changes) => mergeExcelChanges(changes, true))
+      .catch((err) => console.error("Eroare la verificarea fișierelor Excel:", err));
+
+  const dropExcelChange = (change) =>
+    setExcelChanges((prev) => prev.filter((c) => c !== change));
+
+  // Actualizează lista din fișier (progresul cerințelor potrivite se păstrează)
+  const applyExcelChange = async (change) => {
+    setApplyingExcel(true);
+    try {
+      await api.applyReimport(change.projectId, change.categoryName, change.excelPath);
+      dropExcelChange(change);
+      fetchDbData();
+    } catch (err) {
+      console.error("Eroare la actualizarea din Excel:", err);
+      alert(`Eroare la actualizarea din Excel: ${err}`);
+    }
+    setApplyingExcel(false);
+  };
+
+  // Lista rămâne cum e; fișierul actual nu mai e semnalat
+  const acknowledgeExcelChange = async (change) => {
+    try {
+      await api.acknowledgeExcelChange(change.projectId, change.categoryName);
+      dropExcelChange(change);
+    } catch (err) {
+      console.error("Eroare la confirmarea modificării Excel:", err);
+      alert(`Eroare: ${err}`);
+    }
+  };
+
   async function fetchDbData() {
     try {
       const data = await api.loadProjects();
@@ -167,6 +218,7 @@ const handleAddYear = async () => {
     loadYears();
     // 2) prima încărcare de proiecte
     fetchDbData();
+    if (IS_TAURI) checkExcelChanges();
 
     // 3) ascultăm evenimente din backend (Tauri-only)
     let cancelled = false;
@@ -178,9 +230,13 @@ const handleAddYear = async () => {
         listen("project_added", () => fetchDbData()).then(u => {
           if (cancelled) { u(); } else { unlistenFns.push(u); }
         });
+        listen("excel_changed", (e) => mergeExcelChanges(e.payload)).then(u => {
+          if (cancelled) { u(); } else { unlistenFns.push(u); }
+        });
         listen("year_switched", (e) => {
           setCurrentYear(e.payload);
           fetchDbData();
+          checkExcelChanges();
         }).then(u => {
           if (cancelled) { u(); } else { unlistenFns.push(u); }
         });
@@ -220,7 +276,16 @@ const handleAddYear = async () => {
   const isProjectExpanded = (projectId) => expandedProjects.includes(projectId);
 
   /* ───────────── open category modal în funcţie de mode ───────────── */
-  const openCategoryModal = (proj, catIdx, mode) => {
+  const openCategoryModal = async (proj, catIdx, mode) => {
+    let category;
+    try {
+      category = await api.loadCategory(proj.id, proj.categories[catIdx].name);
+    } catch (err) {
+      console.error("Eroare la load_category:", err);
+      alert("Eroare la încărcarea categoriei.");
+      return;
+    }
+    setCategoryTasks(category.checklist ?? []);
     setModalMode(mode);
     setSelectedProject(proj);
 
@@ -314,9 +379,10 @@ const handleAddYear = async () => {
   const handlePteConfirm = async (updatedTasks) =>
     saveChecklist(updatedTasks, "pte/pccvi");
   // ① correct parameter order, ② close the modal when done
-const handleTehnicConfirm = async (updatedTasks) => {
-  // save just the tasks (excelPath has already been saved on load/update)
-  await saveChecklist(updatedTasks, "tehnic")
+const handleTehnicConfirm = async (updatedTasks, excelHash = null) => {
+  // save just the tasks (excelPath has already been saved on load/update);
+  // excelHash is set when the tasks were just read from the Excel file
+  await saveChecklist(updatedTasks, "tehnic", excelHash)
   setShowTehnicModal(false)
   fetchDbData();
 }
@@ -357,7 +423,7 @@ function formatMonthDay(fullDate) {
   return `${MM}.${DD}`;
 }
 
-const saveChecklist = async (updatedTasks, catKey) => {
+const saveChecklist = async (updatedTasks, catKey, excelHash = null) => {
     if (!selectedProject) return;
     try {
       // Find the proper category name (preserving case)
@@ -366,21 +432,12 @@ const saveChecklist = async (updatedTasks, catKey) => {
       );
       const categoryName = cat?.name || catKey;
 
-      await api.saveChecklist(selectedProject.id, categoryName, updatedTasks);
+      await api.saveChecklist(selectedProject.id, categoryName, updatedTasks, excelHash);
       alert("Modificări salvate!");
 
-      // Update local state
-      setDbData((prev) => ({
-        projects: prev.projects.map((proj) => {
-          if (proj.id !== selectedProject.id) return proj;
-          return {
-            ...proj,
-            categories: proj.categories.map((c) =>
-              c.name.toLowerCase() !== catKey ? c : { ...c, checklist: updatedTasks }
-            ),
-          };
-        }),
-      }));
+      // Update local state (contoarele vin din backend)
+      setCategoryTasks(updatedTasks);
+      await fetchDbData();
     } catch (err) {
       console.error("Eroare la salvare:", err);
     }
@@ -747,11 +804,7 @@ const saveChecklist = async (updatedTasks, catKey) => {
           onConfirm={handleEligibilityConfirm}
           projectTitle={selectedProject.title}
           userName = {displayName}
-          initialTasks={
-            selectedProject.categories.find(
-              (c) => c.name.toLowerCase() === "eligibilitate"
-            )?.checklist
-          }
+          initialTasks={categoryTasks}
           projectPath={selectedProject.path || null}
         />
       )}
@@ -764,11 +817,7 @@ const saveChecklist = async (updatedTasks, catKey) => {
           onConfirm={handleFinancialConfirm}
           projectTitle={selectedProject.title}
           userName = {displayName}
-          initialTasks={
-            selectedProject.categories.find(
-              (c) => c.name.toLowerCase() === "financiar"
-            )?.checklist
-          }
+          initialTasks={categoryTasks}
           projectPath={selectedProject.path || null}
         />
       )}
@@ -781,11 +830,7 @@ const saveChecklist = async (updatedTasks, catKey) => {
           onConfirm={handlePteConfirm}
           projectTitle={selectedProject.title}
           userName = {displayName}
-          initialTasks={
-            selectedProject.categories.find(
-              (c) => c.name.toLowerCase() === "pte/pccvi"
-            )?.checklist
-          }
+          initialTasks={categoryTasks}
           projectPath={selectedProject.path || null}
         />
       )}
@@ -801,10 +846,7 @@ const saveChecklist = async (updatedTasks, catKey) => {
      projectId={selectedProject.id}
      projectTitle={selectedProject.title}
      userName={displayName}
-     initialTasks={
-       selectedProject.categories.find(c => c.name.toLowerCase() === "tehnic")
-         ?.checklist ?? []
-     }
+     initialTasks={categoryTasks}
      excelPath={
        selectedProject.categories.find(c => c.name.toLowerCase() === "tehnic")
          ?.excelPath ?? null
@@ -1006,6 +1048,76 @@ const saveChecklist = async (updatedTasks, catKey) => {
           </Button>
         </DialogActions>
       </Dialog>
+
+      {/* ═══════════ Dialog: Excel legat modificat ═══════════ */}
+      <Dialog
+        open={excelChanges.length > 0 && !excelChangesHidden}
+        onClose={() => setExcelChangesHidden(true)}
+        maxWidth="md"
+        fullWidth
+      >
+        <DialogTitle>Fișiere Excel modificate</DialogTitle>
+        <DialogContent dividers>
+          {excelChanges.map((change) => {
+            const { diff } = change;
+            const listed = diff.changes.filter(
+              (c) => c.kind !== "unchanged" || c.previousReference
+            );
+            return (
+              <Box
+                key={`${change.projectId}_${change.categoryName}`}
+                sx={{ mb: 2, p: 1.5, border: "1px solid #e5e7eb", borderRadius: 2 }}
+              >
+                <Typography sx={{ fontWeight: 700 }}>
+                  {change.projectTitle} – {change.categoryName}
+                </Typography>
+                <Typography variant="body2" color="text.secondary" sx={{ wordBreak: "break-all" }}>
+                  {change.excelPath}
+                </Typography>
+                <Typography variant="body2" sx={{ mt: 1 }}>
+                  {diff.added} cerințe noi, {diff.renamed} modificate, {diff.removed} eliminate,{" "}
+                  {diff.unchanged} neschimbate.
+                </Typography>
+
+                <Box sx={{ mt: 1, maxHeight: 200, overflowY: "auto" }}>
+                  {listed.map((c, i) => (
+                    <Typography key={i} variant="body2" sx={{ pl: c.depth * 2 }}>
+                      {c.kind === "added" && <strong style={{ color: "seagreen" }}>+ </strong>}
+                      {c.kind === "removed" && <strong style={{ color: "#d32f2f" }}>− </strong>}
+                      {c.kind === "renamed" && <strong style={{ color: "#b26a00" }}>~ </strong>}
+                      {c.reference ? `${c.reference} ` : ""}
+                      {c.kind === "renamed" && c.previousName ? `${c.previousName} → ${c.name}` : c.name}
+                      {c.previousReference && c.kind !== "renamed" ? ` (fost ${c.previousReference})` : ""}
+                      {c.kind === "removed" && c.notes > 0 ? ` (${c.notes} note, mutate în coș)` : ""}
+                    </Typography>
+                  ))}
+                </Box>
+
+                <Box sx={{ display: "flex", gap: 1, justifyContent: "flex-end", mt: 1 }}>
+                  <Button
+                    size="small"
+                    onClick={() => acknowledgeExcelChange(change)}
+                    disabled={applyingExcel}
+                  >
+                    Păstrează lista actuală
+                  </Button>
+                  <Button
+                    size="small"
+                    variant="contained"
+                    onClick={() => applyExcelChange(change)}
+                    disabled={applyingExcel}
+                  >
+                    Actualizează din Excel
+                  </Button>
+                </Box>
+              </Box>
+            );
+          })}
+        </DialogContent>
+        <DialogActions>
+          <Button onClick={() => setExcelChangesHidden(true)}>Mai târziu</Button>
+        </DialogActions>
+      </Dialog>
     </Stack>
   );
 }